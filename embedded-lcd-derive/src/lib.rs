@@ -0,0 +1,198 @@
+//! Derive macros for `embedded-lcd`, re-exported by its `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Ident, Member, Type};
+
+const SIGNALS: [&str; 11] = [
+    "rs", "rw", "en", "d0", "d1", "d2", "d3", "d4", "d5", "d6", "d7",
+];
+
+/// The signals every bus needs, D0 to D3 are only needed in 8-bit mode.
+const REQUIRED: [usize; 7] = [0, 1, 2, 7, 8, 9, 10];
+const LOWER_DATA: [usize; 4] = [3, 4, 5, 6];
+
+/// Implements `LcdParallelPins` for a struct whose fields are marked with `#[lcd(rs)]`,
+/// `#[lcd(rw)]`, `#[lcd(en)]` and `#[lcd(d4)]` to `#[lcd(d7)]`.
+///
+/// If `#[lcd(d0)]` to `#[lcd(d3)]` are present as well, `LcdParallelPins8` is implemented too
+/// and the struct can be used for an 8-bit bus. Use `LcdParallelWriteOnly` for the `rw` field if
+/// RW is tied to ground.
+///
+/// ```ignore
+/// #[derive(LcdParallelPins)]
+/// struct Pins {
+///     #[lcd(rs)]
+///     register_select: Pin<PD2>,
+///     #[lcd(rw)]
+///     read_write: LcdParallelWriteOnly,
+///     #[lcd(en)]
+///     enable: Pin<PD3>,
+///     #[lcd(d4)]
+///     data4: Pin<PD4>,
+///     #[lcd(d5)]
+///     data5: Pin<PD5>,
+///     #[lcd(d6)]
+///     data6: Pin<PD6>,
+///     #[lcd(d7)]
+///     data7: Pin<PD7>,
+/// }
+/// ```
+///
+/// Every signal is assigned to a single field:
+///
+/// ```compile_fail
+/// # use embedded_lcd::bus::LcdParallelPins;
+/// #[derive(LcdParallelPins)]
+/// struct Pins {
+///     #[lcd(rs)]
+///     rs: (),
+///     #[lcd(rw)]
+///     rw: (),
+///     #[lcd(en)]
+///     #[lcd(rs)]
+///     en: (),
+///     #[lcd(d4)]
+///     d4: (),
+///     #[lcd(d5)]
+///     d5: (),
+///     #[lcd(d6)]
+///     d6: (),
+///     #[lcd(d7)]
+///     d7: (),
+/// }
+/// ```
+///
+/// `d0` to `d3` are assigned all together or not at all:
+///
+/// ```compile_fail
+/// # use embedded_lcd::bus::LcdParallelPins;
+/// #[derive(LcdParallelPins)]
+/// struct Pins {
+///     #[lcd(rs)]
+///     rs: (),
+///     #[lcd(rw)]
+///     rw: (),
+///     #[lcd(en)]
+///     en: (),
+///     #[lcd(d0)]
+///     d0: (),
+///     #[lcd(d1)]
+///     d1: (),
+///     #[lcd(d4)]
+///     d4: (),
+///     #[lcd(d5)]
+///     d5: (),
+///     #[lcd(d6)]
+///     d6: (),
+///     #[lcd(d7)]
+///     d7: (),
+/// }
+/// ```
+#[proc_macro_derive(LcdParallelPins, attributes(lcd))]
+pub fn derive_lcd_parallel_pins(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`LcdParallelPins` can only be derived for structs",
+        ));
+    };
+
+    let mut pins: [Option<(Member, Type)>; 11] = Default::default();
+    for (index, field) in data.fields.iter().enumerate() {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("lcd"))
+        {
+            let signal: Ident = attr.parse_args()?;
+            let Some(slot) = SIGNALS.iter().position(|name| signal == name) else {
+                return Err(Error::new_spanned(
+                    &signal,
+                    "expected one of `rs`, `rw`, `en` or `d0` to `d7`",
+                ));
+            };
+            if pins[slot].is_some() {
+                return Err(Error::new_spanned(
+                    &signal,
+                    format_args!("`{signal}` is assigned to more than one field"),
+                ));
+            }
+
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            pins[slot] = Some((member, field.ty.clone()));
+        }
+    }
+
+    for slot in REQUIRED {
+        if pins[slot].is_none() {
+            return Err(Error::new(
+                Span::call_site(),
+                format_args!("missing a field marked with `#[lcd({})]`", SIGNALS[slot]),
+            ));
+        }
+    }
+    let lower_data = LOWER_DATA
+        .iter()
+        .filter(|&&slot| pins[slot].is_some())
+        .count();
+    if lower_data != 0 && lower_data != LOWER_DATA.len() {
+        return Err(Error::new(
+            Span::call_site(),
+            "either all or none of `d0` to `d3` must be assigned",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let pins_trait = quote!(::embedded_lcd::bus::LcdParallelPins);
+    let items = |slots: &[usize]| {
+        slots
+            .iter()
+            .map(|&slot| {
+                let (member, ty) = pins[slot].as_ref().unwrap();
+                let assoc = format_ident!("{}", SIGNALS[slot].to_uppercase());
+                let method = format_ident!("{}", SIGNALS[slot]);
+                quote! {
+                    type #assoc = #ty;
+
+                    #[inline(always)]
+                    fn #method(&mut self) -> &mut Self::#assoc {
+                        &mut self.#member
+                    }
+                }
+            })
+            .collect::<TokenStream2>()
+    };
+
+    let upper = items(&REQUIRED);
+    let mut expanded = quote! {
+        impl #impl_generics #pins_trait for #ident #ty_generics #where_clause {
+            #upper
+        }
+    };
+
+    if lower_data != 0 {
+        let lower = items(&LOWER_DATA);
+        expanded.extend(quote! {
+            impl #impl_generics ::embedded_lcd::bus::LcdParallelPins8 for #ident #ty_generics
+                #where_clause
+            {
+                #lower
+            }
+        });
+    }
+
+    Ok(expanded)
+}
@@ -0,0 +1,47 @@
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    asynch::{AsyncLcdDriver, AsyncLcdWrite},
+    bus::asynch::LcdWrite,
+    CharsetWithFallback, DisplayMemoryMap, LcdDriver, LcdError,
+};
+
+use super::BufferedLcd;
+
+impl<B, M, C, const W: usize, const H: usize> BufferedLcd<LcdDriver<B, M, C>, W, H>
+where
+    M: DisplayMemoryMap,
+    C: CharsetWithFallback,
+{
+    /// Sends the cells that changed since the last flush.
+    ///
+    /// The address is only set when the address counter does not already point at the next
+    /// changed cell.
+    pub async fn flush_async<Delay>(&mut self, delay: &mut Delay) -> Result<(), LcdError<B::Error>>
+    where
+        B: LcdWrite<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        for y in self.row_order() {
+            for x in 0..W {
+                let code = self.pending[y][x];
+                if self.synced && self.shown[y][x] == code {
+                    continue;
+                }
+                let Some(address) = self.driver.memory_map().address_for_xy(x as u8, y as u8)
+                else {
+                    continue;
+                };
+
+                if self.driver.address() != address {
+                    self.driver.set_address(address, delay).await?;
+                }
+                self.driver.write_code(code, delay).await?;
+                self.shown[y][x] = code;
+            }
+        }
+
+        self.synced = true;
+        Ok(())
+    }
+}
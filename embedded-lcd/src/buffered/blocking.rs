@@ -0,0 +1,84 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    blocking::{BlockingLcdDriver, BlockingLcdWrite},
+    bus::blocking::LcdWrite,
+    CharsetWithFallback, DisplayMemoryMap, LcdDriver, LcdError,
+};
+
+use super::BufferedLcd;
+
+impl<B, M, C, const W: usize, const H: usize> BufferedLcd<LcdDriver<B, M, C>, W, H>
+where
+    M: DisplayMemoryMap,
+    C: CharsetWithFallback,
+{
+    /// Sends the cells that changed since the last flush.
+    ///
+    /// The address is only set when the address counter does not already point at the next
+    /// changed cell.
+    pub fn flush<Delay>(&mut self, delay: &mut Delay) -> Result<(), LcdError<B::Error>>
+    where
+        B: LcdWrite<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        for y in self.row_order() {
+            for x in 0..W {
+                let code = self.pending[y][x];
+                if self.synced && self.shown[y][x] == code {
+                    continue;
+                }
+                let Some(address) = self.driver.memory_map().address_for_xy(x as u8, y as u8)
+                else {
+                    continue;
+                };
+
+                if self.driver.address() != address {
+                    self.driver.set_address(address, delay)?;
+                }
+                self.driver.write_code(code, delay)?;
+                self.shown[y][x] = code;
+            }
+        }
+
+        self.synced = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        blocking::BlockingLcdDriverInit,
+        mock::{CountingBus, NoDelay},
+        BufferedLcd, LcdDriver, LcdDriverOptions, MemoryMap2004,
+    };
+
+    #[test]
+    fn test_flush_only_changed_cells() {
+        let bus = CountingBus::default();
+        let driver = LcdDriver::init(
+            LcdDriverOptions::new(bus.clone(), MemoryMap2004::new()),
+            &mut NoDelay,
+        )
+        .unwrap();
+        let mut lcd = BufferedLcd::<_, 20, 4>::new(driver);
+
+        // The third line follows the first one in DDRAM.
+        lcd.write_at(0, 0, "01234567890123456789");
+        lcd.write_at(0, 2, "01234567890123456789");
+        lcd.flush(&mut NoDelay).unwrap();
+        assert_eq!(0, bus.addresses());
+        assert_eq!(40, bus.writes());
+
+        lcd.flush(&mut NoDelay).unwrap();
+        assert_eq!(0, bus.addresses());
+        assert_eq!(40, bus.writes());
+
+        lcd.write_at(3, 1, "ab");
+        lcd.write_at(6, 1, "c");
+        lcd.flush(&mut NoDelay).unwrap();
+        assert_eq!(2, bus.addresses());
+        assert_eq!(43, bus.writes());
+    }
+}
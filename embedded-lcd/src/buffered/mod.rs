@@ -0,0 +1,105 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use crate::{CharsetWithFallback, DisplayMemoryMap, LcdDriver};
+
+/// Keeps the intended contents of the display in RAM and only sends the changed cells on flush.
+///
+/// The buffer assumes that the display is blank when it is created. If the display is written to
+/// through [`BufferedLcd::driver`], call [`BufferedLcd::invalidate`] to redraw everything on the
+/// next flush.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BufferedLcd<D, const W: usize, const H: usize> {
+    driver: D,
+    /// The contents that were last sent to the display.
+    shown: [[u8; W]; H],
+    /// The contents that are sent to the display on the next flush.
+    pending: [[u8; W]; H],
+    /// Whether `shown` is known to match the display.
+    synced: bool,
+}
+
+impl<D, const W: usize, const H: usize> BufferedLcd<D, W, H> {
+    pub fn new(driver: D) -> Self {
+        Self {
+            driver,
+            shown: [[b' '; W]; H],
+            pending: [[b' '; W]; H],
+            synced: true,
+        }
+    }
+
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    pub fn destroy(self) -> D {
+        self.driver
+    }
+
+    /// Forgets what is shown on the display, so that the next flush sends every cell.
+    pub fn invalidate(&mut self) {
+        self.synced = false;
+    }
+
+    /// Fills the buffer with spaces.
+    pub fn clear(&mut self) {
+        self.pending = [[b' '; W]; H];
+    }
+
+    /// The character code of a cell in the buffer.
+    pub fn code_at(&self, x: u8, y: u8) -> Option<u8> {
+        self.pending
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .copied()
+    }
+
+    /// Sets the character code of a cell as is, e.g. to show a custom glyph. Cells outside of the
+    /// buffer are ignored.
+    pub fn write_code_at(&mut self, x: u8, y: u8, code: u8) {
+        if let Some(cell) = self
+            .pending
+            .get_mut(y as usize)
+            .and_then(|row| row.get_mut(x as usize))
+        {
+            *cell = code;
+        }
+    }
+}
+
+impl<B, M, C, const W: usize, const H: usize> BufferedLcd<LcdDriver<B, M, C>, W, H>
+where
+    C: CharsetWithFallback,
+{
+    /// Writes a string into the buffer starting at a cell. Text beyond the end of the line is
+    /// cut off.
+    pub fn write_at(&mut self, x: u8, y: u8, s: &str) {
+        let Some(row) = self.pending.get_mut(y as usize) else {
+            return;
+        };
+        let charset = self.driver.charset();
+        for (cell, ch) in row.iter_mut().skip(x as usize).zip(s.chars()) {
+            *cell = charset.code_from_utf8_with_fallback(ch);
+        }
+    }
+}
+
+impl<B, M, C, const W: usize, const H: usize> BufferedLcd<LcdDriver<B, M, C>, W, H>
+where
+    M: DisplayMemoryMap,
+{
+    /// The rows sorted by their DDRAM address.
+    ///
+    /// Flushing in this order lets the address counter run from the end of one row into the next
+    /// row without setting the address, e.g. from the first into the third line of a 2004.
+    fn row_order(&self) -> [usize; H] {
+        let map = self.driver.memory_map();
+        let mut rows = core::array::from_fn(|y| y);
+        rows.sort_unstable_by_key(|&y| map.address_for_xy(0, y as u8).unwrap_or(u8::MAX));
+        rows
+    }
+}
@@ -0,0 +1,214 @@
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    bus::{
+        asynch::{LcdInit, LcdWrite},
+        AsyncLcdTimingsI2c, LcdInstruction, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+};
+
+use super::{
+    LcdAip31068Bus, Pca963xBacklight, BREATHE_STEPS, CONTROL_COMMAND, GRPFREQ, GRPPWM, LEDOUT,
+    LEDOUT_GROUP, MODE1, MODE2, MODE2_DMBLNK, PWM0,
+};
+
+impl<I, T> LcdAip31068Bus<I, T>
+where
+    I: I2c,
+{
+    /// Sets the PWM of the red, green and blue LEDs of the backlight. Does nothing without a
+    /// backlight.
+    pub async fn set_backlight_rgb_async(&mut self, r: u8, g: u8, b: u8) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        for (led, pwm) in [
+            (backlight.red, r),
+            (backlight.green, g),
+            (backlight.blue, b),
+        ] {
+            self.i2c
+                .write(backlight.address, &[PWM0 + led, pwm])
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Dims all LEDs of the backlight at once and stops blinking.
+    pub async fn set_backlight_brightness_async(&mut self, brightness: u8) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        self.i2c.write(backlight.address, &[MODE2, 0]).await?;
+        self.i2c
+            .write(backlight.address, &[GRPPWM, brightness])
+            .await
+    }
+
+    /// Blinks the backlight with a period of `period_ms`, staying on for `duty` / 256 of it.
+    /// [`set_backlight_brightness_async`](Self::set_backlight_brightness_async) stops blinking.
+    pub async fn blink_backlight_async(
+        &mut self,
+        period_ms: u16,
+        duty: u8,
+    ) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        let frequency = Pca963xBacklight::blink_frequency(period_ms);
+        self.i2c
+            .write(backlight.address, &[GRPFREQ, frequency])
+            .await?;
+        self.i2c.write(backlight.address, &[GRPPWM, duty]).await?;
+        self.i2c
+            .write(backlight.address, &[MODE2, MODE2_DMBLNK])
+            .await
+    }
+
+    /// Fades the backlight out and back in once over `period_ms`, without touching its color.
+    /// Calling it in a loop lets the backlight breathe. Stops blinking and ends at full
+    /// brightness.
+    pub async fn breathe_backlight_async<Delay>(
+        &mut self,
+        period_ms: u16,
+        delay: &mut Delay,
+    ) -> Result<(), I::Error>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        let step_us = Pca963xBacklight::breathe_step_us(period_ms);
+        self.i2c.write(backlight.address, &[MODE2, 0]).await?;
+        for step in (1..=BREATHE_STEPS).chain((0..BREATHE_STEPS).rev()) {
+            let brightness = Pca963xBacklight::breathe_brightness(step);
+            self.i2c
+                .write(backlight.address, &[GRPPWM, brightness])
+                .await?;
+            delay.delay_us(step_us).await;
+        }
+        Ok(())
+    }
+
+    /// Wakes the backlight driver up and turns all LEDs fully on.
+    async fn init_backlight_async(&mut self, backlight: Pca963xBacklight) -> Result<(), I::Error> {
+        self.i2c.write(backlight.address, &[MODE1, 0]).await?;
+        self.i2c
+            .write(backlight.address, &[LEDOUT, LEDOUT_GROUP])
+            .await?;
+        self.set_backlight_brightness_async(0xff).await?;
+        self.set_backlight_rgb_async(0xff, 0xff, 0xff).await
+    }
+}
+
+impl<I, T, Delay> LcdWrite<Delay> for LcdAip31068Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = I::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.i2c
+            .write(self.address, &[Self::control(rs), data])
+            .await?;
+
+        let instruction = LcdInstruction::decode(rs, data);
+        self.timings.enable_pulse_on(instruction, delay).await;
+        self.timings.enable_pulse_off(instruction, delay).await;
+        Ok(())
+    }
+}
+
+impl<I, T, Delay> LcdInit<Delay> for LcdAip31068Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.timings.power_on_delay(delay).await;
+
+        // The controller always receives whole bytes.
+        let function_set = crate::FUNCTION_SET
+            | function
+                .union(LcdFunctionMode::DATA_LENGTH)
+                .intersection(LcdFunctionMode::all())
+                .bits();
+
+        self.i2c
+            .write(self.address, &[CONTROL_COMMAND, function_set])
+            .await?;
+        self.timings.first_init_delay(delay).await;
+        self.i2c
+            .write(self.address, &[CONTROL_COMMAND, function_set])
+            .await?;
+        self.timings.second_init_delay(delay).await;
+        self.write_command(function_set, delay).await?;
+
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(crate::CLEAR_DISPLAY, delay).await?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )
+        .await?;
+
+        if let Some(backlight) = self.backlight {
+            self.init_backlight_async(backlight).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use crate::{
+        bus::{asynch::LcdWrite, LcdAip31068Bus, Pca963xBacklight},
+        mock::{block_on, MockI2c, NoDelay},
+    };
+
+    use super::PWM0;
+
+    #[test]
+    fn test_control_bytes_and_backlight() {
+        let mut bus =
+            LcdAip31068Bus::new(MockI2c::new(), 0x3e).with_backlight(Pca963xBacklight::WAVESHARE);
+        block_on(bus.write_command(crate::CLEAR_DISPLAY, &mut NoDelay)).unwrap();
+        block_on(bus.write_memory(0x41, &mut NoDelay)).unwrap();
+        block_on(bus.set_backlight_rgb_async(0x10, 0x20, 0x30)).unwrap();
+
+        let i2c = bus.destroy();
+        assert_eq!(
+            vec![vec![0x80, crate::CLEAR_DISPLAY], vec![0x40, 0x41]],
+            i2c.writes(0x3e)
+        );
+        assert_eq!(
+            vec![vec![PWM0 + 2, 0x10], vec![PWM0 + 1, 0x20], vec![PWM0, 0x30]],
+            i2c.writes(0x60)
+        );
+    }
+}
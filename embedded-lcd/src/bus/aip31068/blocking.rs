@@ -0,0 +1,242 @@
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    bus::{
+        blocking::{LcdInit, LcdWrite},
+        LcdInstruction, LcdRegisterSelect, LcdTimingsI2c,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+};
+
+use super::{
+    LcdAip31068Bus, Pca963xBacklight, BREATHE_STEPS, CONTROL_COMMAND, GRPFREQ, GRPPWM, LEDOUT,
+    LEDOUT_GROUP, MODE1, MODE2, MODE2_DMBLNK, PWM0,
+};
+
+impl<I, T> LcdAip31068Bus<I, T>
+where
+    I: I2c,
+{
+    /// Sets the PWM of the red, green and blue LEDs of the backlight. Does nothing without a
+    /// backlight.
+    pub fn set_backlight_rgb(&mut self, r: u8, g: u8, b: u8) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        for (led, pwm) in [
+            (backlight.red, r),
+            (backlight.green, g),
+            (backlight.blue, b),
+        ] {
+            self.i2c.write(backlight.address, &[PWM0 + led, pwm])?;
+        }
+        Ok(())
+    }
+
+    /// Dims all LEDs of the backlight at once and stops blinking.
+    pub fn set_backlight_brightness(&mut self, brightness: u8) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        self.i2c.write(backlight.address, &[MODE2, 0])?;
+        self.i2c.write(backlight.address, &[GRPPWM, brightness])
+    }
+
+    /// Blinks the backlight with a period of `period_ms`, staying on for `duty` / 256 of it.
+    /// [`set_backlight_brightness`](Self::set_backlight_brightness) stops blinking.
+    pub fn blink_backlight(&mut self, period_ms: u16, duty: u8) -> Result<(), I::Error> {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        let frequency = Pca963xBacklight::blink_frequency(period_ms);
+        self.i2c.write(backlight.address, &[GRPFREQ, frequency])?;
+        self.i2c.write(backlight.address, &[GRPPWM, duty])?;
+        self.i2c.write(backlight.address, &[MODE2, MODE2_DMBLNK])
+    }
+
+    /// Fades the backlight out and back in once over `period_ms`, without touching its color.
+    /// Calling it in a loop lets the backlight breathe. Stops blinking and ends at full
+    /// brightness.
+    pub fn breathe_backlight<Delay>(
+        &mut self,
+        period_ms: u16,
+        delay: &mut Delay,
+    ) -> Result<(), I::Error>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        let Some(backlight) = self.backlight else {
+            return Ok(());
+        };
+        let step_us = Pca963xBacklight::breathe_step_us(period_ms);
+        self.i2c.write(backlight.address, &[MODE2, 0])?;
+        for step in (1..=BREATHE_STEPS).chain((0..BREATHE_STEPS).rev()) {
+            let brightness = Pca963xBacklight::breathe_brightness(step);
+            self.i2c.write(backlight.address, &[GRPPWM, brightness])?;
+            delay.delay_us(step_us);
+        }
+        Ok(())
+    }
+
+    /// Wakes the backlight driver up and turns all LEDs fully on.
+    fn init_backlight(&mut self, backlight: Pca963xBacklight) -> Result<(), I::Error> {
+        self.i2c.write(backlight.address, &[MODE1, 0])?;
+        self.i2c.write(backlight.address, &[LEDOUT, LEDOUT_GROUP])?;
+        self.set_backlight_brightness(0xff)?;
+        self.set_backlight_rgb(0xff, 0xff, 0xff)
+    }
+}
+
+impl<I, T, Delay> LcdWrite<Delay> for LcdAip31068Bus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = I::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.i2c.write(self.address, &[Self::control(rs), data])?;
+
+        let instruction = LcdInstruction::decode(rs, data);
+        self.timings.enable_pulse_on(instruction, delay);
+        self.timings.enable_pulse_off(instruction, delay);
+        Ok(())
+    }
+}
+
+impl<I, T, Delay> LcdInit<Delay> for LcdAip31068Bus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.timings.power_on_delay(delay);
+
+        // The controller always receives whole bytes.
+        let function_set = crate::FUNCTION_SET
+            | function
+                .union(LcdFunctionMode::DATA_LENGTH)
+                .intersection(LcdFunctionMode::all())
+                .bits();
+
+        self.i2c
+            .write(self.address, &[CONTROL_COMMAND, function_set])?;
+        self.timings.first_init_delay(delay);
+        self.i2c
+            .write(self.address, &[CONTROL_COMMAND, function_set])?;
+        self.timings.second_init_delay(delay);
+        self.write_command(function_set, delay)?;
+
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )?;
+        self.write_command(crate::CLEAR_DISPLAY, delay)?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )?;
+
+        if let Some(backlight) = self.backlight {
+            self.init_backlight(backlight)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use crate::{
+        bus::{blocking::LcdWrite, LcdAip31068Bus, Pca963xBacklight},
+        mock::{MockI2c, NoDelay},
+    };
+
+    use super::{GRPFREQ, GRPPWM, MODE2, MODE2_DMBLNK, PWM0};
+
+    fn bus() -> LcdAip31068Bus<MockI2c, crate::bus::timings::DefaultTimingsI2c> {
+        LcdAip31068Bus::new(
+            MockI2c::new(),
+            LcdAip31068Bus::<MockI2c, _>::DEFAULT_ADDRESS,
+        )
+        .with_backlight(Pca963xBacklight::GROVE)
+    }
+
+    #[test]
+    fn test_control_bytes() {
+        let mut bus = bus();
+        bus.write_command(crate::CLEAR_DISPLAY, &mut NoDelay)
+            .unwrap();
+        bus.write_memory(0x41, &mut NoDelay).unwrap();
+
+        assert_eq!(
+            vec![vec![0x80, crate::CLEAR_DISPLAY], vec![0x40, 0x41]],
+            bus.destroy().writes(0x3e)
+        );
+    }
+
+    #[test]
+    fn test_backlight_registers() {
+        let mut bus = bus();
+        bus.set_backlight_rgb(0x10, 0x20, 0x30).unwrap();
+        bus.blink_backlight(1000, 0x80).unwrap();
+
+        assert_eq!(
+            vec![
+                vec![PWM0 + 2, 0x10],
+                vec![PWM0 + 1, 0x20],
+                vec![PWM0, 0x30],
+                vec![GRPFREQ, 23],
+                vec![GRPPWM, 0x80],
+                vec![MODE2, MODE2_DMBLNK],
+            ],
+            bus.destroy().writes(0x62)
+        );
+    }
+
+    #[test]
+    fn test_breathe_fades_out_and_in() {
+        let mut bus = bus();
+        bus.breathe_backlight(2000, &mut NoDelay).unwrap();
+
+        let writes = bus.destroy().writes(0x62);
+        assert_eq!(vec![MODE2, 0], writes[0]);
+        let brightness: Vec<u8> = writes[1..]
+            .iter()
+            .map(|write| {
+                assert_eq!(GRPPWM, write[0]);
+                write[1]
+            })
+            .collect();
+        assert_eq!(64, brightness.len());
+        assert_eq!(Some(&0), brightness.iter().min());
+        assert_eq!(Some(&0xff), brightness.last());
+        assert!(brightness[..32].windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(brightness[32..].windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_no_backlight() {
+        let mut bus = LcdAip31068Bus::new(MockI2c::new(), 0x3e);
+        bus.set_backlight_rgb(0x10, 0x20, 0x30).unwrap();
+        bus.blink_backlight(1000, 0x80).unwrap();
+        assert!(bus.destroy().ops.is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use crate::bus::timings::DefaultTimingsI2c;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use super::LcdRegisterSelect;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+mod registers {
+    /// The control byte of a single command, with the continuation bit set.
+    pub(super) const CONTROL_COMMAND: u8 = 0x80;
+    /// The control byte of data, which may be followed by more data bytes.
+    pub(super) const CONTROL_DATA: u8 = 0x40;
+
+    pub(super) const MODE1: u8 = 0x00;
+    pub(super) const MODE2: u8 = 0x01;
+    pub(super) const PWM0: u8 = 0x02;
+    pub(super) const GRPPWM: u8 = 0x06;
+    pub(super) const GRPFREQ: u8 = 0x07;
+    pub(super) const LEDOUT: u8 = 0x08;
+
+    /// Makes GRPPWM and GRPFREQ blink the LEDs instead of dimming them.
+    pub(super) const MODE2_DMBLNK: u8 = 0x20;
+    /// Every LED is driven by its own PWM and the group PWM.
+    pub(super) const LEDOUT_GROUP: u8 = 0xff;
+    /// The GRPPWM steps of fading the backlight out or back in.
+    pub(super) const BREATHE_STEPS: u8 = 32;
+}
+#[cfg(any(feature = "blocking", feature = "async"))]
+use registers::*;
+
+/// The PCA9633 or PCA9632 that drives an RGB backlight, and the outputs of its LEDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pca963xBacklight {
+    pub address: u8,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Pca963xBacklight {
+    /// The Grove LCD RGB Backlight up to version 4, with blue on PWM0, green on PWM1 and red on
+    /// PWM2.
+    pub const GROVE: Self = Self {
+        address: 0x62,
+        red: 2,
+        green: 1,
+        blue: 0,
+    };
+
+    /// The Waveshare LCD1602 RGB module, wired like the [`GROVE`](Self::GROVE) one.
+    pub const WAVESHARE: Self = Self {
+        address: 0x60,
+        ..Self::GROVE
+    };
+
+    /// The GRPFREQ value of a blink period, which is a multiple of 1/24 s from 42 ms to 10.7 s.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[inline]
+    const fn blink_frequency(period_ms: u16) -> u8 {
+        let steps = period_ms as u32 * 24 / 1000;
+        if steps == 0 {
+            0
+        } else if steps > 256 {
+            255
+        } else {
+            (steps - 1) as u8
+        }
+    }
+
+    /// The GRPPWM value `step` steps into fading out from full brightness.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[inline]
+    const fn breathe_brightness(step: u8) -> u8 {
+        (255 - step as u16 * 255 / BREATHE_STEPS as u16) as u8
+    }
+
+    /// The time between two steps of a breath with a period of `period_ms`.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[inline]
+    const fn breathe_step_us(period_ms: u16) -> u32 {
+        period_ms as u32 * 1000 / (2 * BREATHE_STEPS as u32)
+    }
+}
+
+impl Default for Pca963xBacklight {
+    fn default() -> Self {
+        Self::GROVE
+    }
+}
+
+/// An LCD with an AiP31068 controller that is addressed over I2C directly, such as the Grove
+/// LCD RGB Backlight and the Waveshare LCD1602 RGB module.
+///
+/// Every write is a control byte that selects the register, followed by the byte. The RGB
+/// backlight of these modules is a separate [`Pca963xBacklight`] on the same bus, which is
+/// initialized along with the LCD.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdAip31068Bus<I, T> {
+    i2c: I,
+    address: u8,
+    backlight: Option<Pca963xBacklight>,
+    timings: T,
+}
+
+impl<I> LcdAip31068Bus<I, DefaultTimingsI2c> {
+    pub const DEFAULT_ADDRESS: u8 = 0x3e;
+
+    #[inline]
+    pub fn new(i2c: I, address: u8) -> Self {
+        Self::new_with_timings(i2c, address, DefaultTimingsI2c)
+    }
+}
+
+impl<I, T> LcdAip31068Bus<I, T> {
+    #[inline]
+    pub fn new_with_timings(i2c: I, address: u8, timings: T) -> Self {
+        Self {
+            i2c,
+            address,
+            backlight: None,
+            timings,
+        }
+    }
+
+    /// Drives an RGB backlight on the same bus, see [`Pca963xBacklight::GROVE`].
+    #[inline]
+    pub fn with_backlight(self, backlight: Pca963xBacklight) -> Self {
+        Self {
+            backlight: Some(backlight),
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn backlight(&self) -> Option<&Pca963xBacklight> {
+        self.backlight.as_ref()
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        &self.timings
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.timings = timings;
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdAip31068Bus<I, T2> {
+        LcdAip31068Bus {
+            i2c: self.i2c,
+            address: self.address,
+            backlight: self.backlight,
+            timings,
+        }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> I {
+        self.i2c
+    }
+
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    #[inline(always)]
+    fn control(rs: LcdRegisterSelect) -> u8 {
+        match rs {
+            LcdRegisterSelect::Control => CONTROL_COMMAND,
+            LcdRegisterSelect::Memory => CONTROL_DATA,
+        }
+    }
+}
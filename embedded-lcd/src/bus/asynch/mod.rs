@@ -0,0 +1,137 @@
+use crate::driver::{LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus};
+
+use super::{LcdInstruction, LcdNibbleLevels, LcdRegisterSelect};
+
+#[allow(async_fn_in_trait)]
+pub trait LcdWrite<Delay: ?Sized> {
+    type Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+
+    async fn write_command(&mut self, data: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.write(LcdRegisterSelect::Control, data, delay).await
+    }
+
+    async fn write_memory(&mut self, data: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.write(LcdRegisterSelect::Memory, data, delay).await
+    }
+
+    /// Sets the DDRAM address. A bus with two controllers selects the second one when the upper
+    /// bit of `address` is set, see [`LcdDualControllerBus`](crate::bus::LcdDualControllerBus).
+    async fn write_ddram_address(
+        &mut self,
+        address: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_command(crate::SET_DDRAM_ADDRESS | address, delay)
+            .await
+    }
+}
+
+#[allow(async_fn_in_trait)]
+pub trait LcdRead<Delay: ?Sized>: LcdWrite<Delay> {
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error>;
+
+    /// Reads from the DDRAM or CGRAM at the address counter, which is advanced afterwards.
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error>;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait LcdInit<Delay: ?Sized>: LcdWrite<Delay> {
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display_mode: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+}
+
+/// A bus that can leave waiting for the execution of an instruction to the caller, which allows
+/// polling the busy flag instead of waiting for the worst-case execution time.
+///
+/// See [`LcdBusyFlagBus`](crate::bus::LcdBusyFlagBus).
+#[allow(async_fn_in_trait)]
+pub trait LcdBusyFlag<Delay: ?Sized>: LcdWrite<Delay> {
+    /// Writes to a register without waiting for the instruction to be executed.
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+
+    /// Reads the busy flag as fast as the controller allows.
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error>;
+
+    /// Waits for the fixed worst-case execution time of an instruction.
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay);
+
+    /// The least time [`is_busy`](Self::is_busy) takes in µs, which counts against the timeout
+    /// along with the poll interval.
+    fn poll_duration_us(&self) -> u32 {
+        0
+    }
+
+    /// Sets the DDRAM address without waiting for the instruction to be executed, see
+    /// [`LcdWrite::write_ddram_address`].
+    async fn write_ddram_address_no_wait(
+        &mut self,
+        address: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_no_wait(
+            LcdRegisterSelect::Control,
+            crate::SET_DDRAM_ADDRESS | address,
+            delay,
+        )
+        .await
+    }
+}
+
+/// How the outputs of a shift register are loaded, see
+/// [`LcdShiftRegisterBus`](crate::bus::LcdShiftRegisterBus).
+#[allow(async_fn_in_trait)]
+pub trait LcdShiftRegisterWiring {
+    type Error;
+
+    /// Presents `outputs` on the outputs of the register while the enable line stays low.
+    async fn shift_out(&mut self, outputs: u8, enable: u8) -> Result<(), Self::Error>;
+
+    /// Raises or lowers the enable line, leaving the other outputs as they are.
+    async fn set_enable(&mut self, outputs: u8, enable: u8, high: bool) -> Result<(), Self::Error>;
+}
+
+/// Access to the registers of an MCP23008 or MCP23S08, see
+/// [`LcdMcp23008Bus`](crate::bus::LcdMcp23008Bus).
+#[allow(async_fn_in_trait)]
+pub trait LcdMcp23008Transport {
+    type Error;
+
+    /// Writes values to a register one after another.
+    async fn write_register(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error>;
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, Self::Error>;
+}
+
+/// Puts the signals of a 4-bit bus on the wire, see [`LcdNibbleBus`](crate::bus::LcdNibbleBus).
+#[allow(async_fn_in_trait)]
+pub trait LcdNibbleTransport {
+    type Error;
+
+    /// Puts each of `levels` on the wire in turn.
+    async fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error>;
+}
+
+/// A 4-bit transport that can also sample D4 to D7.
+#[allow(async_fn_in_trait)]
+pub trait LcdNibbleTransportRead: LcdNibbleTransport {
+    /// Samples D4 to D7 into the lower nibble, while the last levels written have `read` and
+    /// `enable` set.
+    async fn read(&mut self) -> Result<u8, Self::Error>;
+}
@@ -0,0 +1,98 @@
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{
+        asynch::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        LcdInstruction, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::LcdBusyFlagBus;
+
+impl<B> LcdBusyFlagBus<B> {
+    /// Polls the busy flag until `instruction` is executed, or waits the fixed delay of
+    /// `instruction` once the timeout runs out.
+    async fn wait_ready_async<Delay>(
+        &mut self,
+        instruction: LcdInstruction,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        B: LcdBusyFlag<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let mut waited: u32 = 0;
+        while self.bus.is_busy(delay).await? {
+            waited = waited.saturating_add(self.bus.poll_duration_us());
+            if waited >= self.timeout_us {
+                self.bus.wait_fixed(instruction, delay).await;
+                self.timeouts = self.timeouts.saturating_add(1);
+                break;
+            }
+            delay.delay_us(Self::POLL_INTERVAL_US).await;
+            waited = waited.saturating_add(Self::POLL_INTERVAL_US);
+        }
+
+        Ok(())
+    }
+}
+
+impl<B, Delay> LcdWrite<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = B::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write_no_wait(rs, data, delay).await?;
+        self.wait_ready_async(LcdInstruction::decode(rs, data), delay)
+            .await
+    }
+
+    async fn write_ddram_address(
+        &mut self,
+        address: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write_ddram_address_no_wait(address, delay).await?;
+        self.wait_ready_async(LcdInstruction::Command, delay).await
+    }
+}
+
+impl<B, Delay> LcdRead<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay> + LcdRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.read_status(delay).await
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay).await
+    }
+}
+
+impl<B, Delay> LcdInit<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay> + LcdInit<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    /// The busy flag cannot be checked during initialization, so the fixed delays are used.
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display_mode: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.init(function, display_mode, entry, delay).await
+    }
+}
@@ -0,0 +1,268 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    bus::{
+        blocking::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        LcdInstruction, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::LcdBusyFlagBus;
+
+impl<B> LcdBusyFlagBus<B> {
+    /// Polls the busy flag until `instruction` is executed, or waits the fixed delay of
+    /// `instruction` once the timeout runs out.
+    fn wait_ready<Delay>(
+        &mut self,
+        instruction: LcdInstruction,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        B: LcdBusyFlag<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let mut waited: u32 = 0;
+        while self.bus.is_busy(delay)? {
+            waited = waited.saturating_add(self.bus.poll_duration_us());
+            if waited >= self.timeout_us {
+                self.bus.wait_fixed(instruction, delay);
+                self.timeouts = self.timeouts.saturating_add(1);
+                break;
+            }
+            delay.delay_us(Self::POLL_INTERVAL_US);
+            waited = waited.saturating_add(Self::POLL_INTERVAL_US);
+        }
+
+        Ok(())
+    }
+}
+
+impl<B, Delay> LcdWrite<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = B::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write_no_wait(rs, data, delay)?;
+        self.wait_ready(LcdInstruction::decode(rs, data), delay)
+    }
+
+    fn write_ddram_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus.write_ddram_address_no_wait(address, delay)?;
+        self.wait_ready(LcdInstruction::Command, delay)
+    }
+}
+
+impl<B, Delay> LcdRead<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay> + LcdRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.read_status(delay)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay)
+    }
+}
+
+impl<B, Delay> LcdInit<Delay> for LcdBusyFlagBus<B>
+where
+    B: LcdBusyFlag<Delay> + LcdInit<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    /// The busy flag cannot be checked during initialization, so the fixed delays are used.
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display_mode: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.init(function, display_mode, entry, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::convert::Infallible;
+    use std::{vec, vec::Vec};
+
+    use crate::{
+        bus::{
+            blocking::{LcdBusyFlag, LcdWrite},
+            LcdBusyFlagBus, LcdController, LcdDualControllerBus, LcdEnableSelect, LcdInstruction,
+            LcdRegisterSelect,
+        },
+        mock::NoDelay,
+    };
+
+    /// Stays busy for `busy` polls, each taking `poll_duration_us`.
+    struct BusyBus {
+        busy: u32,
+        polls: u32,
+        poll_duration_us: u32,
+        waited_fixed: bool,
+    }
+
+    impl BusyBus {
+        fn new(busy: u32, poll_duration_us: u32) -> Self {
+            Self {
+                busy,
+                polls: 0,
+                poll_duration_us,
+                waited_fixed: false,
+            }
+        }
+    }
+
+    impl LcdWrite<NoDelay> for BusyBus {
+        type Error = Infallible;
+
+        fn write(
+            &mut self,
+            _rs: LcdRegisterSelect,
+            _data: u8,
+            _delay: &mut NoDelay,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl LcdBusyFlag<NoDelay> for BusyBus {
+        fn write_no_wait(
+            &mut self,
+            _rs: LcdRegisterSelect,
+            _data: u8,
+            _delay: &mut NoDelay,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn is_busy(&mut self, _delay: &mut NoDelay) -> Result<bool, Self::Error> {
+            self.polls += 1;
+            Ok(self.polls <= self.busy)
+        }
+
+        fn wait_fixed(&mut self, _instruction: LcdInstruction, _delay: &mut NoDelay) {
+            self.waited_fixed = true;
+        }
+
+        fn poll_duration_us(&self) -> u32 {
+            self.poll_duration_us
+        }
+    }
+
+    #[test]
+    fn test_polls_until_ready() {
+        let mut bus = LcdBusyFlagBus::new(BusyBus::new(3, 0));
+        assert_eq!(Ok(()), bus.write_memory(0x41, &mut NoDelay));
+        assert_eq!(4, bus.bus().polls);
+        assert!(!bus.bus().waited_fixed);
+        assert_eq!(0, bus.timeouts());
+    }
+
+    #[test]
+    fn test_timeout_falls_back_to_fixed_delay() {
+        let mut bus = LcdBusyFlagBus::new_with_timeout(BusyBus::new(u32::MAX, 0), 100);
+        assert_eq!(Ok(()), bus.write_memory(0x41, &mut NoDelay));
+        assert_eq!(11, bus.bus().polls);
+        assert!(bus.bus().waited_fixed);
+        assert_eq!(1, bus.timeouts());
+    }
+
+    #[test]
+    fn test_timeout_counts_poll_duration() {
+        // Each poll takes 90 µs on the bus and 10 µs of interval.
+        let mut bus = LcdBusyFlagBus::new_with_timeout(BusyBus::new(u32::MAX, 90), 1000);
+        assert_eq!(Ok(()), bus.write_memory(0x41, &mut NoDelay));
+        assert_eq!(11, bus.bus().polls);
+        assert_eq!(1, bus.timeouts());
+    }
+
+    /// Records the controller that every write and poll goes to.
+    #[derive(Default)]
+    struct DualBus {
+        selected: Option<LcdController>,
+        writes: Vec<(LcdController, u8, bool)>,
+        polls: Vec<LcdController>,
+    }
+
+    impl LcdEnableSelect for DualBus {
+        fn select(&mut self, controller: LcdController) {
+            self.selected = Some(controller);
+        }
+    }
+
+    impl LcdWrite<NoDelay> for DualBus {
+        type Error = Infallible;
+
+        fn write(
+            &mut self,
+            _rs: LcdRegisterSelect,
+            data: u8,
+            _delay: &mut NoDelay,
+        ) -> Result<(), Self::Error> {
+            self.writes.push((self.selected.unwrap(), data, true));
+            Ok(())
+        }
+    }
+
+    impl LcdBusyFlag<NoDelay> for DualBus {
+        fn write_no_wait(
+            &mut self,
+            _rs: LcdRegisterSelect,
+            data: u8,
+            _delay: &mut NoDelay,
+        ) -> Result<(), Self::Error> {
+            self.writes.push((self.selected.unwrap(), data, false));
+            Ok(())
+        }
+
+        fn is_busy(&mut self, _delay: &mut NoDelay) -> Result<bool, Self::Error> {
+            self.polls.push(self.selected.unwrap());
+            Ok(false)
+        }
+
+        fn wait_fixed(&mut self, _instruction: LcdInstruction, _delay: &mut NoDelay) {}
+    }
+
+    #[test]
+    fn test_select_is_forwarded() {
+        let mut bus = LcdBusyFlagBus::new(DualBus::default());
+        bus.select(LcdController::Second);
+        assert_eq!(Some(LcdController::Second), bus.bus().selected);
+    }
+
+    #[test]
+    fn test_ddram_address_selects_controller() {
+        let mut bus = LcdBusyFlagBus::new(LcdDualControllerBus::new(DualBus::default()));
+        assert_eq!(Ok(()), bus.write_ddram_address(0x80 | 0x14, &mut NoDelay));
+
+        let dual = bus.bus();
+        assert_eq!(LcdController::Second, dual.controller());
+        assert_eq!(
+            vec![(
+                LcdController::Second,
+                crate::SET_DDRAM_ADDRESS | 0x94,
+                false
+            )],
+            dual.bus().writes
+        );
+        assert_eq!(
+            vec![LcdController::First, LcdController::Second],
+            dual.bus().polls
+        );
+    }
+}
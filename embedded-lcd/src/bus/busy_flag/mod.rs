@@ -0,0 +1,88 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use super::{LcdController, LcdEnableSelect};
+
+/// Polls the busy flag after every write instead of waiting for the worst-case execution time of
+/// the instruction.
+///
+/// Most instructions finish within 37 µs, while the fixed delays have to account for the slowest
+/// ones. The inner bus must be able to read from the controller, which requires the RW line to be
+/// connected. If the busy flag is still set after the timeout, the fixed delay of the instruction
+/// is waited for instead and counted in [`timeouts`](Self::timeouts).
+///
+/// A display with two controllers is polled through the
+/// [`LcdDualControllerBus`](crate::bus::LcdDualControllerBus) that this bus wraps, which reads
+/// the busy flag of one controller at a time.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdBusyFlagBus<B> {
+    bus: B,
+    timeout_us: u32,
+    timeouts: u32,
+}
+
+impl<B> LcdBusyFlagBus<B> {
+    /// Time between two reads of the busy flag.
+    pub const POLL_INTERVAL_US: u32 = 10;
+
+    /// Covers the slowest instructions (clear display and return home) with some headroom.
+    ///
+    /// Every poll counts against the timeout with the poll interval and the `poll_duration_us`
+    /// of the inner bus.
+    pub const DEFAULT_TIMEOUT_US: u32 = 2000;
+
+    #[inline]
+    pub fn new(bus: B) -> Self {
+        Self::new_with_timeout(bus, Self::DEFAULT_TIMEOUT_US)
+    }
+
+    #[inline]
+    pub fn new_with_timeout(bus: B, timeout_us: u32) -> Self {
+        Self {
+            bus,
+            timeout_us,
+            timeouts: 0,
+        }
+    }
+
+    #[inline]
+    pub fn timeout_us(&self) -> u32 {
+        self.timeout_us
+    }
+
+    #[inline]
+    pub fn set_timeout_us(&mut self, timeout_us: u32) {
+        self.timeout_us = timeout_us;
+    }
+
+    /// The number of writes that fell back to the fixed delay because the busy flag was still
+    /// set after the timeout.
+    #[inline]
+    pub fn timeouts(&self) -> u32 {
+        self.timeouts
+    }
+
+    #[inline]
+    pub fn bus(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    #[inline]
+    pub fn destroy(self) -> B {
+        self.bus
+    }
+}
+
+impl<B> LcdEnableSelect for LcdBusyFlagBus<B>
+where
+    B: LcdEnableSelect,
+{
+    #[inline]
+    fn select(&mut self, controller: LcdController) {
+        self.bus.select(controller);
+    }
+}
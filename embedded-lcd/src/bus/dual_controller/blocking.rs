@@ -0,0 +1,293 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    bus::{
+        blocking::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        LcdInstruction, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{LcdController, LcdDualControllerBus, LcdEnableSelect};
+
+/// How the last instruction of a write is sent, while the ones before it always wait for their
+/// execution.
+trait LastWrite<B, Delay: ?Sized>
+where
+    B: LcdWrite<Delay>,
+{
+    fn send(
+        bus: &mut B,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>;
+}
+
+/// Waits for the execution with the fixed delays of the inner bus.
+struct Wait;
+
+/// Leaves waiting for the execution to the caller, see [`LcdBusyFlag`].
+struct NoWait;
+
+impl<B, Delay> LastWrite<B, Delay> for Wait
+where
+    B: LcdWrite<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    fn send(
+        bus: &mut B,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error> {
+        bus.write(rs, data, delay)
+    }
+}
+
+impl<B, Delay> LastWrite<B, Delay> for NoWait
+where
+    B: LcdBusyFlag<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    fn send(
+        bus: &mut B,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error> {
+        bus.write_no_wait(rs, data, delay)
+    }
+}
+
+impl<B> LcdDualControllerBus<B>
+where
+    B: LcdEnableSelect,
+{
+    #[inline(always)]
+    fn write_to<S, Delay>(
+        &mut self,
+        controller: LcdController,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        S: LastWrite<B, Delay>,
+        B: LcdWrite<Delay>,
+        Delay: ?Sized,
+    {
+        self.bus.select(controller);
+        S::send(&mut self.bus, rs, data, delay)
+    }
+
+    /// Writes the display mode to both controllers, with the cursor only on the selected one.
+    fn write_display_mode<S, Delay>(&mut self, delay: &mut Delay) -> Result<(), B::Error>
+    where
+        S: LastWrite<B, Delay>,
+        B: LcdWrite<Delay>,
+        Delay: ?Sized,
+    {
+        let (active, other) = self.cursor_controllers();
+        let (shown, hidden) = self.display_controls();
+        self.write_to::<Wait, _>(other, LcdRegisterSelect::Control, hidden, delay)?;
+        self.write_to::<S, _>(active, LcdRegisterSelect::Control, shown, delay)
+    }
+
+    /// Makes `controller` the target of DDRAM accesses and moves the cursor to it.
+    fn select_controller<Delay>(
+        &mut self,
+        controller: LcdController,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        B: LcdWrite<Delay>,
+        Delay: ?Sized,
+    {
+        if self.controller == controller {
+            return Ok(());
+        }
+        self.controller = controller;
+        if self.shows_cursor() {
+            self.write_display_mode::<Wait, _>(delay)?;
+        }
+        Ok(())
+    }
+
+    /// Routes a write to the controllers it applies to.
+    fn route<S, Delay>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        S: LastWrite<B, Delay>,
+        B: LcdWrite<Delay>,
+        Delay: ?Sized,
+    {
+        use LcdRegisterSelect::{Control, Memory};
+
+        match (rs, data) {
+            (Memory, _) if self.cgram => {
+                self.write_to::<S, _>(LcdController::Both, rs, data, delay)
+            }
+            (Memory, _) => self.write_to::<S, _>(self.controller, rs, data, delay),
+            (Control, 0x80..) => {
+                self.cgram = false;
+                self.write_to::<S, _>(self.controller, rs, data, delay)
+            }
+            (Control, 0x40..) => {
+                self.cgram = true;
+                self.write_to::<S, _>(LcdController::Both, rs, data, delay)
+            }
+            (Control, 0x08..0x10) => {
+                self.display_mode = LcdDisplayMode::from_bits_truncate(data);
+                self.write_display_mode::<S, _>(delay)
+            }
+            // Clearing the display and returning home set the address of both controllers to 0.
+            // The cursor can only be moved to the first controller once they are done.
+            (Control, 0x01..0x04) => {
+                self.cgram = false;
+                if self.controller != LcdController::First && self.shows_cursor() {
+                    self.write_to::<Wait, _>(LcdController::Both, rs, data, delay)?;
+                    self.select_controller(LcdController::First, delay)
+                } else {
+                    self.controller = LcdController::First;
+                    self.write_to::<S, _>(LcdController::Both, rs, data, delay)
+                }
+            }
+            (Control, _) => self.write_to::<S, _>(LcdController::Both, rs, data, delay),
+        }
+    }
+
+    /// Selects the controller by the upper bit of `address` and sets its DDRAM address.
+    fn route_ddram_address<S, Delay>(
+        &mut self,
+        address: u8,
+        delay: &mut Delay,
+    ) -> Result<(), B::Error>
+    where
+        S: LastWrite<B, Delay>,
+        B: LcdWrite<Delay>,
+        Delay: ?Sized,
+    {
+        let controller = if address & 0x80 != 0 {
+            LcdController::Second
+        } else {
+            LcdController::First
+        };
+        self.select_controller(controller, delay)?;
+        self.route::<S, _>(
+            LcdRegisterSelect::Control,
+            crate::SET_DDRAM_ADDRESS | address,
+            delay,
+        )
+    }
+}
+
+impl<B, Delay> LcdWrite<Delay> for LcdDualControllerBus<B>
+where
+    B: LcdWrite<Delay> + LcdEnableSelect,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = B::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.route::<Wait, _>(rs, data, delay)
+    }
+
+    fn write_ddram_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.route_ddram_address::<Wait, _>(address, delay)
+    }
+}
+
+impl<B, Delay> LcdBusyFlag<Delay> for LcdDualControllerBus<B>
+where
+    B: LcdBusyFlag<Delay> + LcdEnableSelect,
+    Delay: DelayNs + ?Sized,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.route::<NoWait, _>(rs, data, delay)
+    }
+
+    /// Polls the controllers one after another, as both would drive the data lines at once.
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        self.bus.select(LcdController::First);
+        if self.bus.is_busy(delay)? {
+            return Ok(true);
+        }
+        self.bus.select(LcdController::Second);
+        self.bus.is_busy(delay)
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.bus.wait_fixed(instruction, delay);
+    }
+
+    fn poll_duration_us(&self) -> u32 {
+        self.bus.poll_duration_us().saturating_mul(2)
+    }
+
+    fn write_ddram_address_no_wait(
+        &mut self,
+        address: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.route_ddram_address::<NoWait, _>(address, delay)
+    }
+}
+
+impl<B, Delay> LcdRead<Delay> for LcdDualControllerBus<B>
+where
+    B: LcdRead<Delay> + LcdEnableSelect,
+    Delay: DelayNs + ?Sized,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.select(self.controller);
+        self.bus.read_status(delay)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.select(self.controller);
+        self.bus.read_data(delay)
+    }
+}
+
+impl<B, Delay> LcdInit<Delay> for LcdDualControllerBus<B>
+where
+    B: LcdInit<Delay> + LcdEnableSelect,
+    Delay: DelayNs + ?Sized,
+{
+    /// Initializes both controllers at once.
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display_mode: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.select(LcdController::Both);
+        self.bus.init(function, display_mode, entry, delay)?;
+
+        self.controller = LcdController::First;
+        self.display_mode = display_mode;
+        self.cgram = false;
+        if self.shows_cursor() {
+            self.write_display_mode::<Wait, _>(delay)?;
+        }
+        Ok(())
+    }
+}
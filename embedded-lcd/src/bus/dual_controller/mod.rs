@@ -0,0 +1,182 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::LcdDisplayMode;
+
+use super::{LcdParallelError, LcdParallelSignal};
+
+/// The controllers of a display with two HD44780 that enable pulses go to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdController {
+    /// The controller of the first two rows, behind E1.
+    First,
+    /// The controller of the last two rows, behind E2.
+    Second,
+    Both,
+}
+
+/// A bus with an enable line per controller, see [`LcdDualControllerBus`].
+pub trait LcdEnableSelect {
+    /// Routes the following enable pulses to `controller`.
+    fn select(&mut self, controller: LcdController);
+}
+
+/// The E1 and E2 pins of a display with two controllers, used as the enable pin of a parallel
+/// bus. Only the selected pins are raised.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdDualEnable<E1, E2> {
+    e1: E1,
+    e2: E2,
+    selected: LcdController,
+}
+
+impl<E1, E2> LcdDualEnable<E1, E2> {
+    /// Pulses both enable lines until another controller is selected.
+    #[inline]
+    pub fn new(e1: E1, e2: E2) -> Self {
+        Self {
+            e1,
+            e2,
+            selected: LcdController::Both,
+        }
+    }
+
+    #[inline]
+    pub fn selected(&self) -> LcdController {
+        self.selected
+    }
+
+    #[inline]
+    pub fn destroy(self) -> (E1, E2) {
+        (self.e1, self.e2)
+    }
+}
+
+impl<E1, E2> LcdEnableSelect for LcdDualEnable<E1, E2> {
+    #[inline]
+    fn select(&mut self, controller: LcdController) {
+        self.selected = controller;
+    }
+}
+
+impl<E1, E2> ErrorType for LcdDualEnable<E1, E2> {
+    type Error = LcdParallelError;
+}
+
+impl<E1, E2> OutputPin for LcdDualEnable<E1, E2>
+where
+    E1: OutputPin,
+    E2: OutputPin,
+{
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        if self.selected != LcdController::Second {
+            self.e1
+                .set_high()
+                .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        }
+        if self.selected != LcdController::First {
+            self.e2
+                .set_high()
+                .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        }
+        Ok(())
+    }
+
+    /// Lowers both enable lines, whichever controller is selected.
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.e1
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        self.e2
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))
+    }
+}
+
+/// Drives a display with two controllers, such as a 4004, as one surface.
+///
+/// Initialization and most commands go to both controllers, while DDRAM accesses go to the
+/// controller selected by the upper bit of the last DDRAM address, see
+/// [`DualControllerMemoryMap`](crate::DualControllerMemoryMap). CGRAM writes go to both
+/// controllers, so custom glyphs are available on every row. Only the selected controller shows
+/// the cursor.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdDualControllerBus<B> {
+    bus: B,
+    /// The controller that DDRAM accesses go to, never [`LcdController::Both`].
+    controller: LcdController,
+    // Only the blocking and async buses route writes.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    display_mode: LcdDisplayMode,
+    /// Whether memory accesses go to the CGRAM.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    cgram: bool,
+}
+
+impl<B> LcdDualControllerBus<B> {
+    #[inline]
+    pub fn new(bus: B) -> Self {
+        Self {
+            bus,
+            controller: LcdController::First,
+            #[cfg(any(feature = "blocking", feature = "async"))]
+            display_mode: LcdDisplayMode::empty(),
+            #[cfg(any(feature = "blocking", feature = "async"))]
+            cgram: false,
+        }
+    }
+
+    /// The controller that DDRAM accesses go to.
+    #[inline]
+    pub fn controller(&self) -> LcdController {
+        self.controller
+    }
+
+    #[inline]
+    pub fn bus(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    #[inline]
+    pub fn destroy(self) -> B {
+        self.bus
+    }
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+impl<B> LcdDualControllerBus<B> {
+    /// The controller that shows the cursor and the other one.
+    #[inline(always)]
+    fn cursor_controllers(&self) -> (LcdController, LcdController) {
+        match self.controller {
+            LcdController::Second => (LcdController::Second, LcdController::First),
+            _ => (LcdController::First, LcdController::Second),
+        }
+    }
+
+    /// The display control commands with and without the cursor.
+    #[inline(always)]
+    fn display_controls(&self) -> (u8, u8) {
+        let cursor = LcdDisplayMode::SHOW_CURSOR | LcdDisplayMode::SHOW_CURSOR_POSITION;
+        (
+            crate::DISPLAY_CONTROL | self.display_mode.bits(),
+            crate::DISPLAY_CONTROL | self.display_mode.difference(cursor).bits(),
+        )
+    }
+
+    #[inline(always)]
+    fn shows_cursor(&self) -> bool {
+        self.display_mode.show_cursor() || self.display_mode.show_cursor_position()
+    }
+}
@@ -0,0 +1,332 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{Error as _, ErrorKind, I2c},
+};
+
+use crate::{
+    bus::{
+        asynch::{
+            LcdBusyFlag, LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite,
+        },
+        nibble::asynch::write_byte_async,
+        timings::DefaultTimingsI2c,
+        AsyncLcdTimingsI2c, LcdInstruction, LcdNibbleLevels, LcdPace, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    LcdI2c8574Bus, LcdI2c8574DetectError, LcdI2c8574DetectFailure, Pcf8574, Pcf8574Mapping,
+    Pcf8574TransferMode, DETECT_ADDRESSES, POLL_DURATION_US, PROBE_ADDRESSES,
+};
+
+impl<I> LcdNibbleTransport for Pcf8574<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    async fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let mut outputs = [0; 4];
+        for levels in levels.chunks(outputs.len()) {
+            for (output, levels) in outputs.iter_mut().zip(levels) {
+                *output = self.mapping.levels(*levels, self.backlight);
+            }
+            self.i2c
+                .write(self.address, &outputs[..levels.len()])
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransportRead for Pcf8574<I>
+where
+    I: I2c,
+{
+    async fn read(&mut self) -> Result<u8, Self::Error> {
+        let mut read = 0;
+        self.i2c
+            .read(self.address, core::slice::from_mut(&mut read))
+            .await?;
+        Ok(self.mapping.nibble(read))
+    }
+}
+
+impl<I, T> LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+{
+    pub async fn set_backlight_async(&mut self, on: bool) -> Result<(), I::Error> {
+        let expander = self.bus.transport_mut();
+        expander.backlight = on;
+        expander
+            .write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+            .await
+    }
+
+    /// Writes a byte with the transfer mode, waiting as long as `pace` asks for.
+    async fn write_byte_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        pace: LcdPace,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), I::Error>
+    where
+        T: AsyncLcdTimingsI2c<Delay>,
+    {
+        let (expander, timings) = self.bus.parts_mut();
+
+        if self.transfer_mode == Pcf8574TransferMode::Paced {
+            return write_byte_async(expander, timings, rs, pace, data, delay).await;
+        }
+
+        let transfer = expander.mapping.byte_transfer(rs, expander.backlight, data);
+        expander.i2c.write(expander.address, &transfer).await?;
+        if let LcdPace::Execute(
+            instruction @ (LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome),
+        ) = pace
+        {
+            timings.enable_pulse_on(instruction, delay).await;
+            timings.enable_pulse_off(instruction, delay).await;
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdI2c8574Bus<I, DefaultTimingsI2c>
+where
+    I: I2c,
+{
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack with the
+    /// [`STANDARD`](Pcf8574Mapping::STANDARD) wiring and an HD44780 behind it.
+    ///
+    /// The LCD is initialized with the display off. On failure, the I2C bus is returned in the
+    /// error.
+    ///
+    /// Every address that acknowledges is initialized and probed with writes until an HD44780
+    /// answers, so other devices in these ranges, such as MCP23008 and MCP23017 expanders at
+    /// 0x20 to 0x27, receive those writes too. On a bus with such devices, use
+    /// [`scan_async`](Self::scan_async) to find the backpack without writing and open it with
+    /// [`new`](Self::new).
+    pub async fn detect_async<Delay>(
+        i2c: I,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        Self::detect_with_mapping_async(i2c, Pcf8574Mapping::STANDARD, delay).await
+    }
+
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack wired as `mapping` and an
+    /// HD44780 behind it, writing to every address that acknowledges like
+    /// [`detect_async`](Self::detect_async).
+    pub async fn detect_with_mapping_async<Delay>(
+        i2c: I,
+        mapping: Pcf8574Mapping,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        Self::detect_with_timings_async(i2c, mapping, DefaultTimingsI2c, delay).await
+    }
+
+    /// The PCF8574 and PCF8574A addresses that acknowledge a read, in the order
+    /// [`detect_async`](Self::detect_async) tries them. Nothing is written to the bus.
+    pub async fn scan_async(i2c: &mut I) -> Result<impl Iterator<Item = u8>, I::Error> {
+        let mut acknowledged = [false; DETECT_ADDRESSES.len()];
+        for (acknowledged, address) in acknowledged.iter_mut().zip(DETECT_ADDRESSES) {
+            match i2c.read(address, &mut [0]).await {
+                Ok(()) => *acknowledged = true,
+                Err(source) if matches!(source.kind(), ErrorKind::NoAcknowledge(_)) => {}
+                Err(source) => return Err(source),
+            }
+        }
+        Ok(DETECT_ADDRESSES
+            .into_iter()
+            .zip(acknowledged)
+            .filter_map(|(address, acknowledged)| acknowledged.then_some(address)))
+    }
+}
+
+impl<I, T> LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+{
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack wired as `mapping` and an
+    /// HD44780 behind it, which is driven with `timings`, writing to every address that
+    /// acknowledges like [`detect_async`](Self::detect_async).
+    pub async fn detect_with_timings_async<Delay>(
+        i2c: I,
+        mapping: Pcf8574Mapping,
+        timings: T,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        T: AsyncLcdTimingsI2c<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let mut bus = Self::with_expander(i2c, 0, timings).with_mapping(mapping);
+        let mut expander = None;
+
+        for address in DETECT_ADDRESSES {
+            let source = match bus.bus.transport_mut().i2c.read(address, &mut [0]).await {
+                Ok(()) => {
+                    expander.get_or_insert(address);
+                    bus.bus.transport_mut().address = address;
+                    match bus.probe_async(delay).await {
+                        Ok(true) => return Ok(bus),
+                        Ok(false) => continue,
+                        Err(source) => source,
+                    }
+                }
+                Err(source) if matches!(source.kind(), ErrorKind::NoAcknowledge(_)) => continue,
+                Err(source) => source,
+            };
+            return Err(LcdI2c8574DetectFailure {
+                i2c: bus.destroy(),
+                source: LcdI2c8574DetectError::Bus(source),
+            });
+        }
+
+        Err(LcdI2c8574DetectFailure {
+            i2c: bus.destroy(),
+            source: match expander {
+                Some(address) => LcdI2c8574DetectError::NotResponding { address },
+                None => LcdI2c8574DetectError::NotFound,
+            },
+        })
+    }
+
+    /// Initializes the LCD and checks that DDRAM addresses read back unchanged.
+    async fn probe_async<Delay>(&mut self, delay: &mut Delay) -> Result<bool, I::Error>
+    where
+        T: AsyncLcdTimingsI2c<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        self.init(
+            LcdFunctionMode::DISPLAY_LINES,
+            LcdDisplayMode::empty(),
+            LcdEntryMode::INCREMENT,
+            delay,
+        )
+        .await?;
+
+        for address in PROBE_ADDRESSES {
+            self.write_command(crate::SET_DDRAM_ADDRESS | address, delay)
+                .await?;
+            let status = self.read_status(delay).await?;
+            if status.busy() || status.address() != address {
+                return Ok(false);
+            }
+        }
+        self.write_command(crate::SET_DDRAM_ADDRESS, delay).await?;
+
+        Ok(true)
+    }
+}
+
+impl<I, T, Delay> LcdWrite<Delay> for LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = I::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        self.write_byte_async(rs, pace, data, delay).await
+    }
+}
+
+impl<I, T, Delay> LcdRead<Delay> for LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.read_status(delay).await
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay).await
+    }
+}
+
+impl<I, T, Delay> LcdBusyFlag<Delay> for LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_byte_async(rs, LcdPace::Cycle, data, delay).await
+    }
+
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        self.bus.is_busy(delay).await
+    }
+
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.bus.wait_fixed(instruction, delay).await;
+    }
+
+    fn poll_duration_us(&self) -> u32 {
+        POLL_DURATION_US
+    }
+}
+
+impl<I, T, Delay> LcdInit<Delay> for LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.init(function, display, entry, delay).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bus::{LcdI2c8574Bus, LcdI2c8574DetectError},
+        mock::{block_on, MockI2c, NoDelay},
+    };
+
+    use super::DETECT_ADDRESSES;
+
+    #[test]
+    fn test_detect_not_responding() {
+        let mut i2c = MockI2c::new();
+        i2c.nack = DETECT_ADDRESSES[1..].into();
+        let failure = block_on(LcdI2c8574Bus::detect_async(i2c, &mut NoDelay))
+            .err()
+            .unwrap();
+        assert_eq!(
+            LcdI2c8574DetectError::NotResponding { address: 0x20 },
+            failure.source
+        );
+    }
+}
@@ -1,69 +1,224 @@
 use embedded_hal::{
     delay::DelayNs,
-    i2c::{I2c, Operation},
+    i2c::{Error as _, ErrorKind, I2c},
 };
 
 use crate::{
     bus::{
-        blocking::{LcdInit, LcdRead, LcdWrite},
-        LcdRegisterSelect, LcdTimingsI2c,
+        blocking::{
+            LcdBusyFlag, LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite,
+        },
+        nibble::blocking::write_byte,
+        timings::DefaultTimingsI2c,
+        LcdInstruction, LcdNibbleLevels, LcdPace, LcdRegisterSelect, LcdTimingsI2c,
     },
     LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
 };
 
-use super::{LcdI2c8574Bus, Pins};
+use super::{
+    LcdI2c8574Bus, LcdI2c8574DetectError, LcdI2c8574DetectFailure, Pcf8574, Pcf8574Mapping,
+    Pcf8574TransferMode, DETECT_ADDRESSES, POLL_DURATION_US, PROBE_ADDRESSES,
+};
+
+impl<I> LcdNibbleTransport for Pcf8574<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let mut outputs = [0; 4];
+        for levels in levels.chunks(outputs.len()) {
+            for (output, levels) in outputs.iter_mut().zip(levels) {
+                *output = self.mapping.levels(*levels, self.backlight);
+            }
+            self.i2c.write(self.address, &outputs[..levels.len()])?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransportRead for Pcf8574<I>
+where
+    I: I2c,
+{
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        let mut read = 0;
+        self.i2c
+            .read(self.address, core::slice::from_mut(&mut read))?;
+        Ok(self.mapping.nibble(read))
+    }
+}
 
 impl<I, T> LcdI2c8574Bus<I, T>
 where
     I: I2c,
 {
     pub fn set_backlight(&mut self, on: bool) -> Result<(), I::Error> {
-        self.state.set(Pins::BACKLIGHT, on);
-        self.i2c.write(self.address, &[self.state.bits()])
+        let expander = self.bus.transport_mut();
+        expander.backlight = on;
+        expander.write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
     }
 
-    fn read_data<Delay: ?Sized>(
+    /// Writes a byte with the transfer mode, waiting as long as `pace` asks for.
+    fn write_byte<Delay: ?Sized>(
         &mut self,
         rs: LcdRegisterSelect,
+        pace: LcdPace,
+        data: u8,
         delay: &mut Delay,
-    ) -> Result<u8, I::Error>
+    ) -> Result<(), I::Error>
     where
         T: LcdTimingsI2c<Delay>,
     {
-        let mut state = self.state;
-        state.set(Pins::REGISTER_SELECT, rs == LcdRegisterSelect::Memory);
+        let (expander, timings) = self.bus.parts_mut();
 
-        let transfer = {
-            let b = state | Pins::DATA | Pins::READ;
-            Pins::bits_array([b, b | Pins::ENABLE])
-        };
-        let mut read = 0;
+        if self.transfer_mode == Pcf8574TransferMode::Paced {
+            return write_byte(expander, timings, rs, pace, data, delay);
+        }
 
-        self.i2c.write(self.address, &transfer)?;
-        self.timings.read_delay(delay);
-        self.i2c.transaction(
-            self.address,
-            &mut [
-                Operation::Read(core::slice::from_mut(&mut read)),
-                Operation::Write(&transfer[..1]),
-            ],
-        )?;
-        self.timings.enable_pulse_off(rs, delay);
-        let mut data = read & 0xf0;
-
-        self.i2c.write(self.address, &transfer)?;
-        self.timings.read_delay(delay);
-        self.i2c.transaction(
-            self.address,
-            &mut [
-                Operation::Read(core::slice::from_mut(&mut read)),
-                Operation::Write(&transfer[..1]),
-            ],
+        let transfer = expander.mapping.byte_transfer(rs, expander.backlight, data);
+        expander.i2c.write(expander.address, &transfer)?;
+        if let LcdPace::Execute(
+            instruction @ (LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome),
+        ) = pace
+        {
+            timings.enable_pulse_on(instruction, delay);
+            timings.enable_pulse_off(instruction, delay);
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdI2c8574Bus<I, DefaultTimingsI2c>
+where
+    I: I2c,
+{
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack with the
+    /// [`STANDARD`](Pcf8574Mapping::STANDARD) wiring and an HD44780 behind it.
+    ///
+    /// The LCD is initialized with the display off. On failure, the I2C bus is returned in the
+    /// error.
+    ///
+    /// Every address that acknowledges is initialized and probed with writes until an HD44780
+    /// answers, so other devices in these ranges, such as MCP23008 and MCP23017 expanders at
+    /// 0x20 to 0x27, receive those writes too. On a bus with such devices, use
+    /// [`scan`](Self::scan) to find the backpack without writing and open it with
+    /// [`new`](Self::new).
+    pub fn detect<Delay>(
+        i2c: I,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        Self::detect_with_mapping(i2c, Pcf8574Mapping::STANDARD, delay)
+    }
+
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack wired as `mapping` and an
+    /// HD44780 behind it, writing to every address that acknowledges like
+    /// [`detect`](Self::detect).
+    pub fn detect_with_mapping<Delay>(
+        i2c: I,
+        mapping: Pcf8574Mapping,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        Delay: DelayNs + ?Sized,
+    {
+        Self::detect_with_timings(i2c, mapping, DefaultTimingsI2c, delay)
+    }
+
+    /// The PCF8574 and PCF8574A addresses that acknowledge a read, in the order
+    /// [`detect`](Self::detect) tries them. Nothing is written to the bus.
+    pub fn scan(i2c: &mut I) -> Result<impl Iterator<Item = u8>, I::Error> {
+        let mut acknowledged = [false; DETECT_ADDRESSES.len()];
+        for (acknowledged, address) in acknowledged.iter_mut().zip(DETECT_ADDRESSES) {
+            match i2c.read(address, &mut [0]) {
+                Ok(()) => *acknowledged = true,
+                Err(source) if matches!(source.kind(), ErrorKind::NoAcknowledge(_)) => {}
+                Err(source) => return Err(source),
+            }
+        }
+        Ok(DETECT_ADDRESSES
+            .into_iter()
+            .zip(acknowledged)
+            .filter_map(|(address, acknowledged)| acknowledged.then_some(address)))
+    }
+}
+
+impl<I, T> LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+{
+    /// Scans the PCF8574 and PCF8574A addresses for a backpack wired as `mapping` and an
+    /// HD44780 behind it, which is driven with `timings`, writing to every address that
+    /// acknowledges like [`detect`](Self::detect).
+    pub fn detect_with_timings<Delay>(
+        i2c: I,
+        mapping: Pcf8574Mapping,
+        timings: T,
+        delay: &mut Delay,
+    ) -> Result<Self, LcdI2c8574DetectFailure<I, I::Error>>
+    where
+        T: LcdTimingsI2c<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let mut bus = Self::with_expander(i2c, 0, timings).with_mapping(mapping);
+        let mut expander = None;
+
+        for address in DETECT_ADDRESSES {
+            let source = match bus.bus.transport_mut().i2c.read(address, &mut [0]) {
+                Ok(()) => {
+                    expander.get_or_insert(address);
+                    bus.bus.transport_mut().address = address;
+                    match bus.probe(delay) {
+                        Ok(true) => return Ok(bus),
+                        Ok(false) => continue,
+                        Err(source) => source,
+                    }
+                }
+                Err(source) if matches!(source.kind(), ErrorKind::NoAcknowledge(_)) => continue,
+                Err(source) => source,
+            };
+            return Err(LcdI2c8574DetectFailure {
+                i2c: bus.destroy(),
+                source: LcdI2c8574DetectError::Bus(source),
+            });
+        }
+
+        Err(LcdI2c8574DetectFailure {
+            i2c: bus.destroy(),
+            source: match expander {
+                Some(address) => LcdI2c8574DetectError::NotResponding { address },
+                None => LcdI2c8574DetectError::NotFound,
+            },
+        })
+    }
+
+    /// Initializes the LCD and checks that DDRAM addresses read back unchanged.
+    fn probe<Delay>(&mut self, delay: &mut Delay) -> Result<bool, I::Error>
+    where
+        T: LcdTimingsI2c<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        self.init(
+            LcdFunctionMode::DISPLAY_LINES,
+            LcdDisplayMode::empty(),
+            LcdEntryMode::INCREMENT,
+            delay,
         )?;
-        self.timings.enable_pulse_off(rs, delay);
-        data |= read >> 4;
 
-        Ok(data)
+        for address in PROBE_ADDRESSES {
+            self.write_command(crate::SET_DDRAM_ADDRESS | address, delay)?;
+            let status = self.read_status(delay)?;
+            if status.busy() || status.address() != address {
+                return Ok(false);
+            }
+        }
+        self.write_command(crate::SET_DDRAM_ADDRESS, delay)?;
+
+        Ok(true)
     }
 }
 
@@ -81,29 +236,8 @@ where
         data: u8,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        let mut state = self.state;
-        state.set(Pins::REGISTER_SELECT, rs == LcdRegisterSelect::Memory);
-
-        let upper = {
-            let b = state | Pins::from_bits_retain(data & 0xf0);
-            Pins::bits_array([b, b | Pins::ENABLE])
-        };
-        let lower = {
-            let b = state | Pins::from_bits_retain(data << 4);
-            Pins::bits_array([b, b | Pins::ENABLE])
-        };
-
-        self.i2c.write(self.address, &upper)?;
-        self.timings.enable_pulse_on(rs, delay);
-        self.i2c.write(self.address, &upper[..1])?;
-        self.timings.enable_pulse_off(rs, delay);
-
-        self.i2c.write(self.address, &lower)?;
-        self.timings.enable_pulse_on(rs, delay);
-        self.i2c.write(self.address, &lower[..1])?;
-        self.timings.enable_pulse_off(rs, delay);
-
-        Ok(())
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        self.write_byte(rs, pace, data, delay)
     }
 }
 
@@ -114,8 +248,39 @@ where
     Delay: DelayNs + ?Sized,
 {
     fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
-        self.read_data(LcdRegisterSelect::Control, delay)
-            .map(LcdStatus::from_bits_retain)
+        self.bus.read_status(delay)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay)
+    }
+}
+
+impl<I, T, Delay> LcdBusyFlag<Delay> for LcdI2c8574Bus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_byte(rs, LcdPace::Cycle, data, delay)
+    }
+
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        self.bus.is_busy(delay)
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.bus.wait_fixed(instruction, delay);
+    }
+
+    fn poll_duration_us(&self) -> u32 {
+        POLL_DURATION_US
     }
 }
 
@@ -132,62 +297,121 @@ where
         entry: LcdEntryMode,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.timings.power_on_delay(delay);
-
-        let transfer = {
-            let b = self.state | Pins::from_bits_retain(0x30);
-            Pins::bits_array([b, b | Pins::ENABLE])
-        };
-
-        self.i2c.write(self.address, &transfer)?;
-        self.timings
-            .enable_pulse_on(LcdRegisterSelect::Control, delay);
-        self.i2c.write(self.address, &transfer[..1])?;
-        self.timings.first_init_delay(delay);
-
-        self.i2c.write(self.address, &transfer[1..])?;
-        self.timings
-            .enable_pulse_on(LcdRegisterSelect::Control, delay);
-        self.i2c.write(self.address, &transfer[..1])?;
-        self.timings.second_init_delay(delay);
-
-        self.i2c.write(self.address, &transfer[1..])?;
-        self.timings
-            .enable_pulse_on(LcdRegisterSelect::Control, delay);
-        self.i2c.write(self.address, &transfer[..1])?;
-        self.timings
-            .enable_pulse_off(LcdRegisterSelect::Control, delay);
-
-        // set 4-bit bus
-        let transfer = {
-            let b = self.state | Pins::from_bits_retain(crate::FUNCTION_SET);
-            Pins::bits_array([b, b | Pins::ENABLE])
-        };
-
-        self.i2c.write(self.address, &transfer)?;
-        self.timings
-            .enable_pulse_on(LcdRegisterSelect::Control, delay);
-        self.i2c.write(self.address, &transfer[..1])?;
-        self.timings
-            .enable_pulse_off(LcdRegisterSelect::Control, delay);
-
-        self.write_command(
-            crate::FUNCTION_SET
-                | function
-                    .intersection(LcdFunctionMode::all().difference(LcdFunctionMode::DATA_LENGTH))
-                    .bits(),
-            delay,
-        )?;
-        self.write_command(
-            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
-            delay,
-        )?;
-        self.write_command(crate::CLEAR_DISPLAY, delay)?;
-        self.write_command(
-            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
-            delay,
-        )?;
+        self.bus.init(function, display, entry, delay)
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use embedded_hal::delay::DelayNs;
+
+    use crate::{
+        bus::{
+            blocking::LcdWrite, LcdI2c8574Bus, LcdI2c8574DetectError, LcdRegisterSelect,
+            Pcf8574TransferMode,
+        },
+        mock::{I2cOp, MockI2c, NoDelay},
+    };
+
+    use super::DETECT_ADDRESSES;
+
+    fn nacking_except(address: Option<u8>) -> MockI2c {
+        let mut i2c = MockI2c::new();
+        i2c.nack = DETECT_ADDRESSES
+            .into_iter()
+            .filter(|&a| Some(a) != address)
+            .collect();
+        i2c
+    }
+
+    #[test]
+    fn test_scan_only_reads() {
+        let mut i2c = MockI2c::new();
+        i2c.nack = DETECT_ADDRESSES
+            .into_iter()
+            .filter(|&a| a != 0x20 && a != 0x27)
+            .collect();
+        let addresses: Vec<u8> = LcdI2c8574Bus::scan(&mut i2c).unwrap().collect();
+
+        assert_eq!(vec![0x20, 0x27], addresses);
+        assert_eq!(vec![I2cOp::Read(0x20, 1), I2cOp::Read(0x27, 1)], i2c.ops);
+    }
+
+    #[test]
+    fn test_detect_not_found() {
+        let failure = LcdI2c8574Bus::detect(nacking_except(None), &mut NoDelay)
+            .err()
+            .unwrap();
+        assert_eq!(LcdI2c8574DetectError::NotFound, failure.source);
+        assert!(failure.i2c.ops.is_empty());
+    }
+
+    #[test]
+    fn test_detect_not_responding() {
+        // An expander without an LCD reads back its own pull-ups instead of the probed
+        // addresses.
+        let mut i2c = nacking_except(Some(0x3f));
+        i2c.reads.extend([0xff; 8]);
+        let failure = LcdI2c8574Bus::detect(i2c, &mut NoDelay).err().unwrap();
+        assert_eq!(
+            LcdI2c8574DetectError::NotResponding { address: 0x3f },
+            failure.source
+        );
+    }
+
+    #[test]
+    fn test_detect_found() {
+        let mut i2c = nacking_except(Some(0x27));
+        // The scan reads one byte, then the status nibbles of 0x15 and 0x4a are read on P4 to
+        // P7.
+        i2c.reads.extend([0xff, 0x10, 0x50, 0x40, 0xa0]);
+        let bus = LcdI2c8574Bus::detect(i2c, &mut NoDelay).unwrap();
+
+        let i2c = bus.destroy();
+        assert!(i2c.reads.is_empty());
+        let addresses: Vec<u8> = i2c
+            .ops
+            .iter()
+            .map(|op| match op {
+                I2cOp::Write(address, _) | I2cOp::Read(address, _) => *address,
+            })
+            .collect();
+        assert!(addresses.iter().all(|&address| address == 0x27));
+    }
+
+    /// Sums up the time waited for.
+    struct CountingDelay(u64);
+
+    impl DelayNs for CountingDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.0 += u64::from(ns);
+        }
+    }
+
+    #[test]
+    fn test_batched_frame() {
+        let mut bus = LcdI2c8574Bus::new(MockI2c::new(), 0x27)
+            .with_transfer_mode(Pcf8574TransferMode::Batched);
+        let mut delay = CountingDelay(0);
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut delay)
+            .unwrap();
+        assert_eq!(0, delay.0);
+
+        // Only clearing the display and returning home outlast the transfer.
+        bus.write(LcdRegisterSelect::Control, crate::CLEAR_DISPLAY, &mut delay)
+            .unwrap();
+        assert!(delay.0 >= 1_520_000);
+
+        assert_eq!(
+            vec![
+                vec![0x49, 0x4d, 0x49, 0x19, 0x1d, 0x19],
+                vec![0x08, 0x0c, 0x08, 0x18, 0x1c, 0x18],
+            ],
+            bus.destroy().writes(0x27)
+        );
     }
 }
@@ -1,95 +1,287 @@
+#[cfg(feature = "async")]
+mod asynch;
 #[cfg(feature = "blocking")]
 mod blocking;
 
-use bitflags::bitflags;
-
 use crate::bus::timings::DefaultTimingsI2c;
 
-use super::LcdTimingsI2c;
+use super::{LcdNibbleBus, LcdNibbleLevels, LcdRegisterSelect, LcdTimingsI2c};
+
+/// The PCF8574 pins the LCD signals are connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pcf8574Mapping {
+    pub register_select: u8,
+    pub read_write: u8,
+    pub enable: u8,
+    pub backlight: u8,
+    /// Whether the backlight is on while its pin is low.
+    pub backlight_active_low: bool,
+    /// The pins connected to D4 to D7.
+    pub data: [u8; 4],
+}
+
+impl Pcf8574Mapping {
+    /// RS on P0, RW on P1, E on P2, the backlight on P3 and D4 to D7 on P4 to P7, as wired on
+    /// the YwRobot LCM1602 and most unbranded backpacks.
+    pub const STANDARD: Self = Self {
+        register_select: 0,
+        read_write: 1,
+        enable: 2,
+        backlight: 3,
+        backlight_active_low: false,
+        data: [4, 5, 6, 7],
+    };
+
+    /// The [`STANDARD`](Self::STANDARD) wiring with a backlight that is switched by a PNP
+    /// transistor and therefore on while P3 is low.
+    pub const STANDARD_ACTIVE_LOW: Self = Self {
+        backlight_active_low: true,
+        ..Self::STANDARD
+    };
+
+    /// D4 to D7 on P0 to P3, E on P4, RW on P5, RS on P6 and an active-low backlight on P7, as
+    /// wired on the mjkdz and GY-LCD-V1 backpacks.
+    pub const MJKDZ: Self = Self {
+        register_select: 6,
+        read_write: 5,
+        enable: 4,
+        backlight: 7,
+        backlight_active_low: true,
+        data: [0, 1, 2, 3],
+    };
+
+    /// The pin states for a nibble with the enable line low.
+    const fn outputs(&self, rs: LcdRegisterSelect, read: bool, backlight: bool, nibble: u8) -> u8 {
+        let mut outputs = 0;
+        if matches!(rs, LcdRegisterSelect::Memory) {
+            outputs |= 1 << self.register_select;
+        }
+        if read {
+            outputs |= 1 << self.read_write;
+        }
+        if backlight != self.backlight_active_low {
+            outputs |= 1 << self.backlight;
+        }
+
+        let mut i = 0;
+        while i < 4 {
+            if nibble & (1 << i) != 0 {
+                outputs |= 1 << self.data[i];
+            }
+            i += 1;
+        }
+        outputs
+    }
+
+    /// The pin states for `levels`. While reading, the data lines are pulled high so the LCD
+    /// can drive them.
+    #[inline]
+    const fn levels(&self, levels: LcdNibbleLevels, backlight: bool) -> u8 {
+        let data = if levels.read { 0x0f } else { levels.data };
+        let outputs = self.outputs(levels.rs, levels.read, backlight, data);
+        if levels.enable {
+            outputs | (1 << self.enable)
+        } else {
+            outputs
+        }
+    }
+
+    /// The pin states of a byte written as a single transfer. Every nibble is presented before
+    /// and held after its enable pulse, which lasts as long as a byte on the I2C bus.
+    #[inline]
+    const fn byte_transfer(&self, rs: LcdRegisterSelect, backlight: bool, data: u8) -> [u8; 6] {
+        let upper = LcdNibbleLevels::write(rs, data >> 4);
+        let lower = LcdNibbleLevels::write(rs, data);
+        [
+            self.levels(upper, backlight),
+            self.levels(upper.enabled(), backlight),
+            self.levels(upper, backlight),
+            self.levels(lower, backlight),
+            self.levels(lower.enabled(), backlight),
+            self.levels(lower, backlight),
+        ]
+    }
+
+    /// The nibble on the data lines of the pin states.
+    const fn nibble(&self, pins: u8) -> u8 {
+        let mut nibble = 0;
+        let mut i = 0;
+        while i < 4 {
+            if pins & (1 << self.data[i]) != 0 {
+                nibble |= 1 << i;
+            }
+            i += 1;
+        }
+        nibble
+    }
+}
+
+impl Default for Pcf8574Mapping {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// How [`LcdI2c8574Bus`] clocks bytes into the LCD.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Pcf8574TransferMode {
+    /// Every edge of the enable line is a separate write, paced by the timings.
+    #[default]
+    Paced,
+    /// Every byte is a single write, and the enable pulse and the execution time of most
+    /// instructions are covered by the time the bytes take on the bus. Only clearing the display
+    /// and returning home are waited for with the timings.
+    ///
+    /// This relies on the bus clock not exceeding the 400 kHz of a fast-mode I2C bus.
+    Batched,
+}
+
+/// The addresses of the PCF8574 followed by those of the PCF8574A.
+const DETECT_ADDRESSES: [u8; 16] = [
+    0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+];
+
+/// The least time a busy flag poll takes. Each nibble is three transfers of seven bytes in total
+/// with the addresses, so a poll is about 140 bit times on a 400 kHz fast-mode bus.
+const POLL_DURATION_US: u32 = 340;
+
+/// DDRAM addresses that are set and read back to check that an HD44780 answers.
+const PROBE_ADDRESSES: [u8; 2] = [0x15, 0x4a];
+
+/// An error of [`LcdI2c8574Bus::detect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdI2c8574DetectError<E> {
+    Bus(E),
+    /// No expander acknowledged at any PCF8574 or PCF8574A address.
+    NotFound,
+    /// An expander acknowledged at `address`, but no HD44780 answered behind it.
+    NotResponding {
+        address: u8,
+    },
+}
 
-bitflags! {
-    #[derive(Clone, Copy)]
-    struct Pins: u8 {
-        const REGISTER_SELECT = 0x01;
-        const READ = 0x02;
-        const ENABLE = 0x04;
-        const BACKLIGHT = 0x08;
-        const DATA = 0xf0;
+impl<E> core::fmt::Display for LcdI2c8574DetectError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(source) => source.fmt(fmt),
+            Self::NotFound => fmt.write_str("no PCF8574 or PCF8574A acknowledged"),
+            Self::NotResponding { address } => {
+                write!(
+                    fmt,
+                    "no HD44780 answered behind the expander at {address:#04x}"
+                )
+            }
+        }
+    }
+}
+
+impl<E> core::error::Error for LcdI2c8574DetectError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Bus(source) => Some(source),
+            _ => None,
+        }
     }
 }
 
-impl core::fmt::Debug for Pins {
+/// The failure of [`LcdI2c8574Bus::detect`], which returns the I2C bus.
+#[derive(Clone, Copy)]
+pub struct LcdI2c8574DetectFailure<I, E> {
+    pub i2c: I,
+    pub source: LcdI2c8574DetectError<E>,
+}
+
+impl<I, E> core::fmt::Debug for LcdI2c8574DetectFailure<I, E>
+where
+    E: core::fmt::Debug,
+{
     fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        fmt.debug_struct("Pins")
-            .field("register_select", &self.contains(Self::REGISTER_SELECT))
-            .field("read", &self.contains(Self::READ))
-            .field("enable", &self.contains(Self::ENABLE))
-            .field("backlight", &self.contains(Self::BACKLIGHT))
-            .field("data", &(self.bits() >> 4))
-            .finish()
+        fmt.debug_struct("LcdI2c8574DetectFailure")
+            .field("source", &self.source)
+            // Do not format the bus to allow using `.unwrap()` on any error
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, E> core::fmt::Display for LcdI2c8574DetectFailure<I, E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.source.fmt(fmt)
+    }
+}
+
+impl<I, E> core::error::Error for LcdI2c8574DetectFailure<I, E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.source)
     }
 }
 
 #[cfg(feature = "ufmt")]
-impl ufmt::uDebug for Pins {
+impl<I, E> ufmt::uDebug for LcdI2c8574DetectFailure<I, E>
+where
+    E: ufmt::uDebug,
+{
     fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
     where
         W: ufmt::uWrite + ?Sized,
     {
-        fmt.debug_struct("Pins")?
-            .field("register_select", &self.contains(Self::REGISTER_SELECT))?
-            .field("read", &self.contains(Self::READ))?
-            .field("enable", &self.contains(Self::ENABLE))?
-            .field("backlight", &self.contains(Self::BACKLIGHT))?
-            .field("data", &(self.bits() >> 4))?
+        fmt.debug_struct("LcdI2c8574DetectFailure")?
+            .field("source", &self.source)?
             .finish()
     }
 }
 
 #[cfg(feature = "defmt")]
-impl defmt::Format for Pins {
+impl<I, E> defmt::Format for LcdI2c8574DetectFailure<I, E>
+where
+    E: defmt::Format,
+{
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
             fmt,
-            "Pins {{ register_select: {}, read: {}, enable: {}, backlight: {}, data: {} }}",
-            self.contains(Self::REGISTER_SELECT),
-            self.contains(Self::READ),
-            self.contains(Self::ENABLE),
-            self.contains(Self::BACKLIGHT),
-            self.bits() >> 4
+            "LcdI2c8574DetectFailure {{ source: {:?}, .. }}",
+            self.source
         )
     }
 }
 
-impl Pins {
-    #[inline(always)]
-    fn bits_array<const N: usize>(pins: [Pins; N]) -> [u8; N] {
-        core::array::from_fn(|i| pins[i].bits())
-    }
-}
-
-impl Default for Pins {
-    fn default() -> Self {
-        Self::BACKLIGHT
-    }
+/// The expander of a [`LcdI2c8574Bus`], which is the
+/// [`LcdNibbleTransport`](crate::bus::blocking::LcdNibbleTransport) of its paced transfers.
+struct Pcf8574<I> {
+    i2c: I,
+    address: u8,
+    mapping: Pcf8574Mapping,
+    backlight: bool,
 }
 
+/// An LCD behind a PCF8574 backpack, a [`LcdNibbleBus`] with a backlight, a choice of
+/// [`Pcf8574Mapping`] and a [`Batched`](Pcf8574TransferMode::Batched) transfer mode.
 pub struct LcdI2c8574Bus<I, T> {
-    i2c: I,
-    address: u8,
-    state: Pins,
-    timings: T,
+    bus: LcdNibbleBus<Pcf8574<I>, T>,
+    transfer_mode: Pcf8574TransferMode,
 }
 
 impl<I> LcdI2c8574Bus<I, DefaultTimingsI2c> {
     #[inline]
     pub fn new(i2c: I, address: u8) -> Self {
-        Self {
-            i2c,
-            address,
-            state: Pins::default(),
-            timings: DefaultTimingsI2c,
-        }
+        Self::with_expander(i2c, address, DefaultTimingsI2c)
     }
 }
 
@@ -99,16 +291,123 @@ impl<I, T> LcdI2c8574Bus<I, T> {
     where
         T: LcdTimingsI2c<Delay>,
     {
-        Self {
+        Self::with_expander(i2c, address, timings)
+    }
+
+    #[inline]
+    fn with_expander(i2c: I, address: u8, timings: T) -> Self {
+        let expander = Pcf8574 {
             i2c,
             address,
-            state: Pins::default(),
-            timings,
+            mapping: Pcf8574Mapping::STANDARD,
+            backlight: true,
+        };
+        Self {
+            bus: LcdNibbleBus::new_with_timings(expander, timings),
+            transfer_mode: Pcf8574TransferMode::Paced,
+        }
+    }
+
+    /// Uses `mapping` for the pins of the backpack instead of [`Pcf8574Mapping::STANDARD`].
+    #[inline]
+    pub fn with_mapping(mut self, mapping: Pcf8574Mapping) -> Self {
+        self.bus.transport_mut().mapping = mapping;
+        self
+    }
+
+    #[inline]
+    pub fn mapping(&self) -> &Pcf8574Mapping {
+        &self.bus.transport().mapping
+    }
+
+    /// Uses `transfer_mode` instead of [`Pcf8574TransferMode::Paced`].
+    #[inline]
+    pub fn with_transfer_mode(self, transfer_mode: Pcf8574TransferMode) -> Self {
+        Self {
+            transfer_mode,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub fn transfer_mode(&self) -> Pcf8574TransferMode {
+        self.transfer_mode
+    }
+
+    #[inline]
+    pub fn set_transfer_mode(&mut self, transfer_mode: Pcf8574TransferMode) {
+        self.transfer_mode = transfer_mode;
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        self.bus.timings()
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.bus.set_timings(timings);
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdI2c8574Bus<I, T2> {
+        LcdI2c8574Bus {
+            bus: self.bus.with_timings(timings),
+            transfer_mode: self.transfer_mode,
         }
     }
 
     #[inline]
     pub fn destroy(self) -> I {
-        self.i2c
+        self.bus.destroy().i2c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bus::{LcdNibbleLevels, LcdRegisterSelect};
+
+    use super::Pcf8574Mapping;
+
+    #[test]
+    fn test_standard_levels() {
+        let mapping = Pcf8574Mapping::STANDARD;
+        let levels = LcdNibbleLevels::write(LcdRegisterSelect::Memory, 0x4);
+        assert_eq!(0x49, mapping.levels(levels, true));
+        assert_eq!(0x4d, mapping.levels(levels.enabled(), true));
+        assert_eq!(0x41, mapping.levels(levels, false));
+
+        // The data lines are released while reading.
+        let levels = LcdNibbleLevels::read(LcdRegisterSelect::Control);
+        assert_eq!(0xf2, mapping.levels(levels, false));
+        assert_eq!(0x5, mapping.nibble(0x5f));
+    }
+
+    #[test]
+    fn test_active_low_backlight() {
+        let levels = LcdNibbleLevels::write(LcdRegisterSelect::Control, 0);
+        assert_eq!(
+            0x00,
+            Pcf8574Mapping::STANDARD_ACTIVE_LOW.levels(levels, true)
+        );
+        assert_eq!(
+            0x08,
+            Pcf8574Mapping::STANDARD_ACTIVE_LOW.levels(levels, false)
+        );
+    }
+
+    #[test]
+    fn test_mjkdz_levels() {
+        let mapping = Pcf8574Mapping::MJKDZ;
+        let levels = LcdNibbleLevels::write(LcdRegisterSelect::Memory, 0x4);
+        assert_eq!(0x44, mapping.levels(levels, true));
+        assert_eq!(0x54, mapping.levels(levels.enabled(), true));
+        assert_eq!(0xc4, mapping.levels(levels, false));
+
+        let levels = LcdNibbleLevels::read(LcdRegisterSelect::Memory);
+        assert_eq!(0x6f, mapping.levels(levels, true));
+        assert_eq!(0xa, mapping.nibble(0xfa));
     }
 }
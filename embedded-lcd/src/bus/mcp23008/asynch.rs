@@ -0,0 +1,203 @@
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{self, I2c},
+    spi::{self, SpiDevice},
+};
+
+use crate::{
+    bus::{
+        asynch::{
+            LcdInit, LcdMcp23008Transport, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead,
+            LcdWrite,
+        },
+        AsyncLcdTimingsI2c, LcdNibbleLevels, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    LcdMcp23008Bus, LcdMcp23008Error, LcdMcp23008I2c, LcdMcp23S08Spi, Mcp23008, GPIO, IOCON,
+    IOCON_SEQOP, IODIR,
+};
+
+impl<I> LcdMcp23008Transport for LcdMcp23008I2c<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    async fn write_register(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    i2c::Operation::Write(&[register]),
+                    i2c::Operation::Write(values),
+                ],
+            )
+            .await
+    }
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
+        let mut value = 0;
+        self.i2c
+            .write_read(self.address, &[register], core::slice::from_mut(&mut value))
+            .await?;
+        Ok(value)
+    }
+}
+
+impl<S> LcdMcp23008Transport for LcdMcp23S08Spi<S>
+where
+    S: SpiDevice,
+{
+    type Error = S::Error;
+
+    async fn write_register(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.spi
+            .transaction(&mut [
+                spi::Operation::Write(&[self.opcode, register]),
+                spi::Operation::Write(values),
+            ])
+            .await
+    }
+
+    async fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
+        let mut value = 0;
+        self.spi
+            .transaction(&mut [
+                spi::Operation::Write(&[self.opcode | 0x01, register]),
+                spi::Operation::Read(core::slice::from_mut(&mut value)),
+            ])
+            .await?;
+        Ok(value)
+    }
+}
+
+impl<X> Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    /// Makes every GPIO an output, presenting the first nibble of the init sequence.
+    async fn configure_async(&mut self) -> Result<(), X::Error> {
+        self.transport.write_register(IOCON, &[IOCON_SEQOP]).await?;
+        let levels = LcdNibbleLevels::write(LcdRegisterSelect::Control, 0x3);
+        let outputs = self.mapping.levels(levels, self.backlight);
+        self.transport.write_register(GPIO, &[outputs]).await?;
+        self.transport.write_register(IODIR, &[0x00]).await?;
+        self.reading = false;
+        Ok(())
+    }
+
+    /// Turns the data lines around if `read` differs from their current direction.
+    async fn set_reading_async(&mut self, read: bool) -> Result<(), X::Error> {
+        if read != self.reading {
+            let iodir = if read { self.mapping.data_mask() } else { 0x00 };
+            self.transport.write_register(IODIR, &[iodir]).await?;
+            self.reading = read;
+        }
+        Ok(())
+    }
+}
+
+impl<X> LcdNibbleTransport for Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    type Error = X::Error;
+
+    async fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let mut outputs = [0; 4];
+        for levels in levels.chunk_by(|a, b| a.read == b.read) {
+            self.set_reading_async(levels[0].read).await?;
+            for levels in levels.chunks(outputs.len()) {
+                for (output, levels) in outputs.iter_mut().zip(levels) {
+                    *output = self.mapping.levels(*levels, self.backlight);
+                }
+                self.transport
+                    .write_register(GPIO, &outputs[..levels.len()])
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<X> LcdNibbleTransportRead for Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    async fn read(&mut self) -> Result<u8, Self::Error> {
+        let gpio = self.transport.read_register(GPIO).await?;
+        Ok(self.mapping.nibble(gpio))
+    }
+}
+
+impl<X, T> LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+{
+    pub async fn set_backlight_async(
+        &mut self,
+        on: bool,
+    ) -> Result<(), LcdMcp23008Error<X::Error>> {
+        let expander = self.bus.transport_mut();
+        expander.backlight = on;
+        Ok(expander
+            .write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+            .await?)
+    }
+}
+
+impl<X, T, Delay> LcdWrite<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdMcp23008Error<X::Error>;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        Ok(self.bus.write(rs, data, delay).await?)
+    }
+}
+
+impl<X, T, Delay> LcdRead<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.check_readable()?;
+        Ok(self.bus.read_status(delay).await?)
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.check_readable()?;
+        Ok(self.bus.read_data(delay).await?)
+    }
+}
+
+impl<X, T, Delay> LcdInit<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.transport_mut().configure_async().await?;
+        Ok(self.bus.init(function, display, entry, delay).await?)
+    }
+}
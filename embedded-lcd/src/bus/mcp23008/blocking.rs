@@ -0,0 +1,302 @@
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{self, I2c},
+    spi::{self, SpiDevice},
+};
+
+use crate::{
+    bus::{
+        blocking::{
+            LcdInit, LcdMcp23008Transport, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead,
+            LcdWrite,
+        },
+        LcdNibbleLevels, LcdRegisterSelect, LcdTimingsI2c,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    LcdMcp23008Bus, LcdMcp23008Error, LcdMcp23008I2c, LcdMcp23S08Spi, Mcp23008, GPIO, IOCON,
+    IOCON_SEQOP, IODIR,
+};
+
+impl<I> LcdMcp23008Transport for LcdMcp23008I2c<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    fn write_register(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.i2c.transaction(
+            self.address,
+            &mut [
+                i2c::Operation::Write(&[register]),
+                i2c::Operation::Write(values),
+            ],
+        )
+    }
+
+    fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
+        let mut value = 0;
+        self.i2c
+            .write_read(self.address, &[register], core::slice::from_mut(&mut value))?;
+        Ok(value)
+    }
+}
+
+impl<S> LcdMcp23008Transport for LcdMcp23S08Spi<S>
+where
+    S: SpiDevice,
+{
+    type Error = S::Error;
+
+    fn write_register(&mut self, register: u8, values: &[u8]) -> Result<(), Self::Error> {
+        self.spi.transaction(&mut [
+            spi::Operation::Write(&[self.opcode, register]),
+            spi::Operation::Write(values),
+        ])
+    }
+
+    fn read_register(&mut self, register: u8) -> Result<u8, Self::Error> {
+        let mut value = 0;
+        self.spi.transaction(&mut [
+            spi::Operation::Write(&[self.opcode | 0x01, register]),
+            spi::Operation::Read(core::slice::from_mut(&mut value)),
+        ])?;
+        Ok(value)
+    }
+}
+
+impl<X> Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    /// Makes every GPIO an output, presenting the first nibble of the init sequence.
+    fn configure(&mut self) -> Result<(), X::Error> {
+        self.transport.write_register(IOCON, &[IOCON_SEQOP])?;
+        let levels = LcdNibbleLevels::write(LcdRegisterSelect::Control, 0x3);
+        let outputs = self.mapping.levels(levels, self.backlight);
+        self.transport.write_register(GPIO, &[outputs])?;
+        self.transport.write_register(IODIR, &[0x00])?;
+        self.reading = false;
+        Ok(())
+    }
+
+    /// Turns the data lines around if `read` differs from their current direction.
+    fn set_reading(&mut self, read: bool) -> Result<(), X::Error> {
+        if read != self.reading {
+            let iodir = if read { self.mapping.data_mask() } else { 0x00 };
+            self.transport.write_register(IODIR, &[iodir])?;
+            self.reading = read;
+        }
+        Ok(())
+    }
+}
+
+impl<X> LcdNibbleTransport for Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    type Error = X::Error;
+
+    fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let mut outputs = [0; 4];
+        for levels in levels.chunk_by(|a, b| a.read == b.read) {
+            self.set_reading(levels[0].read)?;
+            for levels in levels.chunks(outputs.len()) {
+                for (output, levels) in outputs.iter_mut().zip(levels) {
+                    *output = self.mapping.levels(*levels, self.backlight);
+                }
+                self.transport
+                    .write_register(GPIO, &outputs[..levels.len()])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<X> LcdNibbleTransportRead for Mcp23008<X>
+where
+    X: LcdMcp23008Transport,
+{
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        let gpio = self.transport.read_register(GPIO)?;
+        Ok(self.mapping.nibble(gpio))
+    }
+}
+
+impl<X, T> LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+{
+    pub fn set_backlight(&mut self, on: bool) -> Result<(), LcdMcp23008Error<X::Error>> {
+        let expander = self.bus.transport_mut();
+        expander.backlight = on;
+        Ok(expander.write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])?)
+    }
+}
+
+impl<X, T, Delay> LcdWrite<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdMcp23008Error<X::Error>;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        Ok(self.bus.write(rs, data, delay)?)
+    }
+}
+
+impl<X, T, Delay> LcdRead<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.check_readable()?;
+        Ok(self.bus.read_status(delay)?)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.check_readable()?;
+        Ok(self.bus.read_data(delay)?)
+    }
+}
+
+impl<X, T, Delay> LcdInit<Delay> for LcdMcp23008Bus<X, T>
+where
+    X: LcdMcp23008Transport,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.transport_mut().configure()?;
+        Ok(self.bus.init(function, display, entry, delay)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use crate::{
+        bus::{
+            blocking::{LcdInit, LcdRead, LcdWrite},
+            LcdMcp23008Bus, LcdMcp23008Error, LcdMcp23008Mapping, LcdRegisterSelect,
+        },
+        mock::{MockI2c, NoDelay},
+        LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+    };
+
+    use super::{GPIO, IOCON, IODIR};
+
+    const READABLE: LcdMcp23008Mapping = LcdMcp23008Mapping {
+        read_write: Some(0),
+        ..LcdMcp23008Mapping::ADAFRUIT_BACKPACK
+    };
+
+    #[test]
+    fn test_init_configures_outputs() {
+        let mut bus = LcdMcp23008Bus::new_i2c(MockI2c::new(), 0x20, READABLE);
+        bus.init(
+            LcdFunctionMode::DISPLAY_LINES,
+            LcdDisplayMode::SHOW_DISPLAY,
+            LcdEntryMode::INCREMENT,
+            &mut NoDelay,
+        )
+        .unwrap();
+
+        let writes = bus.destroy().destroy().writes(0x20);
+        assert_eq!(
+            vec![
+                vec![IOCON, 0x20],
+                vec![GPIO, 0x98],
+                vec![IODIR, 0x00],
+                vec![GPIO, 0x98, 0x9c],
+                vec![GPIO, 0x98],
+            ],
+            writes[..5]
+        );
+    }
+
+    #[test]
+    fn test_write_sequence() {
+        let mut bus = LcdMcp23008Bus::new_i2c(MockI2c::new(), 0x20, READABLE);
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        // Backlight on GP7, D4 to D7 on GP3 to GP6, E on GP2 and RS on GP1.
+        assert_eq!(
+            vec![
+                vec![GPIO, 0xa2, 0xa6],
+                vec![GPIO, 0xa2],
+                vec![GPIO, 0x8a, 0x8e],
+                vec![GPIO, 0x8a],
+            ],
+            bus.destroy().destroy().writes(0x20)
+        );
+    }
+
+    #[test]
+    fn test_read_switches_data_direction() {
+        let mut i2c = MockI2c::new();
+        i2c.reads.extend([0x20, 0x08]);
+        let mut bus = LcdMcp23008Bus::new_i2c(i2c, 0x20, READABLE);
+        assert_eq!(Ok(0x41), bus.read_data(&mut NoDelay));
+
+        assert_eq!(
+            vec![
+                vec![IODIR, 0x78],
+                vec![GPIO, 0x83, 0x87],
+                vec![GPIO],
+                vec![GPIO, 0x83],
+                vec![GPIO, 0x83, 0x87],
+                vec![GPIO],
+                vec![GPIO, 0x83],
+            ],
+            bus.destroy().destroy().writes(0x20)
+        );
+    }
+
+    #[test]
+    fn test_write_after_read_restores_outputs() {
+        let mut bus = LcdMcp23008Bus::new_i2c(MockI2c::new(), 0x20, READABLE);
+        bus.read_status(&mut NoDelay).unwrap();
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        let writes = bus.destroy().destroy().writes(0x20);
+        assert_eq!(vec![IODIR, 0x78], writes[0]);
+        assert_eq!(
+            vec![vec![IODIR, 0x00], vec![GPIO, 0xa2, 0xa6]],
+            writes[writes.len() - 5..writes.len() - 3]
+        );
+    }
+
+    #[test]
+    fn test_read_without_rw_line() {
+        let mut bus =
+            LcdMcp23008Bus::new_i2c(MockI2c::new(), 0x20, LcdMcp23008Mapping::ADAFRUIT_BACKPACK);
+        assert_eq!(
+            Err(LcdMcp23008Error::NotReadable),
+            bus.read_data(&mut NoDelay)
+        );
+        assert!(bus.destroy().destroy().ops.is_empty());
+    }
+}
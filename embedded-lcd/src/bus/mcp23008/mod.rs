@@ -0,0 +1,280 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use crate::bus::timings::DefaultTimingsI2c;
+
+use super::{LcdNibbleBus, LcdNibbleLevels, LcdRegisterSelect};
+
+const IODIR: u8 = 0x00;
+const IOCON: u8 = 0x05;
+const GPIO: u8 = 0x09;
+
+/// Keeps the address pointer on the same register, so that consecutive writes update the GPIOs
+/// one after another.
+const IOCON_SEQOP: u8 = 0x20;
+
+/// The GPIOs of the expander the LCD signals are connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdMcp23008Mapping {
+    pub register_select: u8,
+    /// The GPIO of the RW line, or `None` if RW is tied to ground and the LCD cannot be read.
+    pub read_write: Option<u8>,
+    pub enable: u8,
+    pub backlight: u8,
+    /// The GPIOs connected to D4 to D7.
+    pub data: [u8; 4],
+}
+
+impl LcdMcp23008Mapping {
+    /// The Adafruit I2C/SPI character LCD backpack, which ties RW to ground.
+    pub const ADAFRUIT_BACKPACK: Self = Self {
+        register_select: 1,
+        read_write: None,
+        enable: 2,
+        backlight: 7,
+        data: [3, 4, 5, 6],
+    };
+
+    /// The GPIO states for a nibble with the enable line low.
+    const fn outputs(&self, rs: LcdRegisterSelect, read: bool, backlight: bool, nibble: u8) -> u8 {
+        let mut outputs = 0;
+        if matches!(rs, LcdRegisterSelect::Memory) {
+            outputs |= 1 << self.register_select;
+        }
+        if let (true, Some(read_write)) = (read, self.read_write) {
+            outputs |= 1 << read_write;
+        }
+        if backlight {
+            outputs |= 1 << self.backlight;
+        }
+
+        let mut i = 0;
+        while i < 4 {
+            if nibble & (1 << i) != 0 {
+                outputs |= 1 << self.data[i];
+            }
+            i += 1;
+        }
+        outputs
+    }
+
+    /// The nibble on the data lines of the GPIO states.
+    const fn nibble(&self, gpio: u8) -> u8 {
+        let mut nibble = 0;
+        let mut i = 0;
+        while i < 4 {
+            if gpio & (1 << self.data[i]) != 0 {
+                nibble |= 1 << i;
+            }
+            i += 1;
+        }
+        nibble
+    }
+
+    /// The GPIO states for `levels`. While reading, the data lines are inputs and their output
+    /// latches are left low.
+    #[inline]
+    const fn levels(&self, levels: LcdNibbleLevels, backlight: bool) -> u8 {
+        let data = if levels.read { 0 } else { levels.data };
+        let outputs = self.outputs(levels.rs, levels.read, backlight, data);
+        if levels.enable {
+            outputs | (1 << self.enable)
+        } else {
+            outputs
+        }
+    }
+
+    /// The direction register with the data lines as inputs.
+    #[inline]
+    const fn data_mask(&self) -> u8 {
+        (1 << self.data[0]) | (1 << self.data[1]) | (1 << self.data[2]) | (1 << self.data[3])
+    }
+}
+
+/// An error of [`LcdMcp23008Bus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdMcp23008Error<E> {
+    Transport(E),
+    /// The mapping has no RW line to read from the LCD.
+    NotReadable,
+}
+
+impl<E> core::fmt::Display for LcdMcp23008Error<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Transport(source) => source.fmt(fmt),
+            Self::NotReadable => fmt.write_str("the RW line of the LCD is not connected"),
+        }
+    }
+}
+
+impl<E> core::error::Error for LcdMcp23008Error<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Transport(source) => Some(source),
+            Self::NotReadable => None,
+        }
+    }
+}
+
+impl<E> From<E> for LcdMcp23008Error<E> {
+    fn from(value: E) -> Self {
+        Self::Transport(value)
+    }
+}
+
+/// An MCP23008 on I2C.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdMcp23008I2c<I> {
+    i2c: I,
+    address: u8,
+}
+
+impl<I> LcdMcp23008I2c<I> {
+    #[inline]
+    pub fn new(i2c: I, address: u8) -> Self {
+        Self { i2c, address }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> I {
+        self.i2c
+    }
+}
+
+/// An MCP23S08 on SPI.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdMcp23S08Spi<S> {
+    spi: S,
+    /// The opcode with the hardware address from the A0 and A1 pins.
+    opcode: u8,
+}
+
+impl<S> LcdMcp23S08Spi<S> {
+    /// Creates the transport for a chip with the hardware address set by its A0 and A1 pins.
+    #[inline]
+    pub fn new(spi: S, hardware_address: u8) -> Self {
+        Self {
+            spi,
+            opcode: 0x40 | ((hardware_address & 0x03) << 1),
+        }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> S {
+        self.spi
+    }
+}
+
+/// The expander of a [`LcdMcp23008Bus`], which is the
+/// [`LcdNibbleTransport`](crate::bus::blocking::LcdNibbleTransport) of its [`LcdNibbleBus`].
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Mcp23008<X> {
+    transport: X,
+    mapping: LcdMcp23008Mapping,
+    backlight: bool,
+    /// Whether the data lines are inputs, which they stay after a read until the next write.
+    reading: bool,
+}
+
+/// An LCD in 4-bit mode behind an MCP23008 or MCP23S08 I/O expander, a [`LcdNibbleBus`] with a
+/// backlight and a choice of [`LcdMcp23008Mapping`].
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdMcp23008Bus<X, T> {
+    bus: LcdNibbleBus<Mcp23008<X>, T>,
+}
+
+impl<I> LcdMcp23008Bus<LcdMcp23008I2c<I>, DefaultTimingsI2c> {
+    #[inline]
+    pub fn new_i2c(i2c: I, address: u8, mapping: LcdMcp23008Mapping) -> Self {
+        Self::new_with_timings(
+            LcdMcp23008I2c::new(i2c, address),
+            mapping,
+            DefaultTimingsI2c,
+        )
+    }
+}
+
+impl<S> LcdMcp23008Bus<LcdMcp23S08Spi<S>, DefaultTimingsI2c> {
+    #[inline]
+    pub fn new_spi(spi: S, hardware_address: u8, mapping: LcdMcp23008Mapping) -> Self {
+        Self::new_with_timings(
+            LcdMcp23S08Spi::new(spi, hardware_address),
+            mapping,
+            DefaultTimingsI2c,
+        )
+    }
+}
+
+impl<X, T> LcdMcp23008Bus<X, T> {
+    #[inline]
+    pub fn new_with_timings(transport: X, mapping: LcdMcp23008Mapping, timings: T) -> Self {
+        let expander = Mcp23008 {
+            transport,
+            mapping,
+            backlight: true,
+            reading: false,
+        };
+        Self {
+            bus: LcdNibbleBus::new_with_timings(expander, timings),
+        }
+    }
+
+    #[inline]
+    pub fn mapping(&self) -> &LcdMcp23008Mapping {
+        &self.bus.transport().mapping
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        self.bus.timings()
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.bus.set_timings(timings);
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdMcp23008Bus<X, T2> {
+        LcdMcp23008Bus {
+            bus: self.bus.with_timings(timings),
+        }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> X {
+        self.bus.destroy().transport
+    }
+
+    /// Fails unless the mapping has an RW line to read from the LCD.
+    #[inline]
+    fn check_readable<E>(&self) -> Result<(), LcdMcp23008Error<E>> {
+        match self.mapping().read_write {
+            Some(_) => Ok(()),
+            None => Err(LcdMcp23008Error::NotReadable),
+        }
+    }
+}
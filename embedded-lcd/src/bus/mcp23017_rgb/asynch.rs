@@ -0,0 +1,157 @@
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    bus::{
+        asynch::{LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite},
+        AsyncLcdTimingsI2c, LcdNibbleLevels, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    LcdMcp23017RgbBus, Mcp23017Rgb, ShieldButtons, DATA, GPIOA, GPIOB, GPPUA, IOCON_BANK1, IODIRA,
+    IODIRB,
+};
+
+impl<I> Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    /// Makes the buttons inputs with pull-ups and everything else an output.
+    async fn configure_async(&mut self) -> Result<(), I::Error> {
+        let buttons = ShieldButtons::all().bits();
+        let port_b = self.port_b(LcdNibbleLevels::write(LcdRegisterSelect::Control, 0));
+        self.i2c.write(self.address, &[IOCON_BANK1, 0x00]).await?;
+        self.i2c.write(self.address, &[GPPUA, buttons]).await?;
+        self.i2c
+            .write(self.address, &[GPIOA, self.port_a()])
+            .await?;
+        self.i2c.write(self.address, &[GPIOB, port_b]).await?;
+        self.i2c.write(self.address, &[IODIRA, buttons]).await?;
+        self.i2c.write(self.address, &[IODIRB, 0x00]).await?;
+        self.reading = false;
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransport for Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    async fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        for levels in levels {
+            if levels.read != self.reading {
+                let iodir = if levels.read { DATA } else { 0x00 };
+                self.i2c.write(self.address, &[IODIRB, iodir]).await?;
+                self.reading = levels.read;
+            }
+            // Every level goes out in its own write, as a second byte would go to OLATA, the
+            // register after GPIOB.
+            self.i2c
+                .write(self.address, &[GPIOB, self.port_b(*levels)])
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransportRead for Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    async fn read(&mut self) -> Result<u8, Self::Error> {
+        let mut gpio = 0;
+        self.i2c
+            .write_read(self.address, &[GPIOB], core::slice::from_mut(&mut gpio))
+            .await?;
+        Ok(Self::nibble(gpio))
+    }
+}
+
+impl<I, T> LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+{
+    /// Switches the red, green and blue LEDs of the backlight on or off.
+    pub async fn set_backlight_rgb_async(
+        &mut self,
+        r: bool,
+        g: bool,
+        b: bool,
+    ) -> Result<(), I::Error> {
+        let expander = self.bus.transport_mut();
+        expander.backlight = Mcp23017Rgb::<I>::backlight_bits(r, g, b);
+        expander
+            .i2c
+            .write(expander.address, &[GPIOA, expander.port_a()])
+            .await?;
+        expander
+            .write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+            .await
+    }
+
+    /// The buttons that are currently pressed.
+    pub async fn read_buttons_async(&mut self) -> Result<ShieldButtons, I::Error> {
+        let expander = self.bus.transport_mut();
+        let mut gpio = 0;
+        expander
+            .i2c
+            .write_read(expander.address, &[GPIOA], core::slice::from_mut(&mut gpio))
+            .await?;
+        // The buttons pull their GPIO to ground.
+        Ok(ShieldButtons::from_bits_truncate(!gpio))
+    }
+}
+
+impl<I, T, Delay> LcdWrite<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = I::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write(rs, data, delay).await
+    }
+}
+
+impl<I, T, Delay> LcdRead<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.read_status(delay).await
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay).await
+    }
+}
+
+impl<I, T, Delay> LcdInit<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.transport_mut().configure_async().await?;
+        self.bus.init(function, display, entry, delay).await
+    }
+}
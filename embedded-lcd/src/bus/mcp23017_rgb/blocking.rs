@@ -0,0 +1,209 @@
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+use crate::{
+    bus::{
+        blocking::{LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite},
+        LcdNibbleLevels, LcdRegisterSelect, LcdTimingsI2c,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    LcdMcp23017RgbBus, Mcp23017Rgb, ShieldButtons, DATA, GPIOA, GPIOB, GPPUA, IOCON_BANK1, IODIRA,
+    IODIRB,
+};
+
+impl<I> Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    /// Makes the buttons inputs with pull-ups and everything else an output.
+    fn configure(&mut self) -> Result<(), I::Error> {
+        let buttons = ShieldButtons::all().bits();
+        let port_b = self.port_b(LcdNibbleLevels::write(LcdRegisterSelect::Control, 0));
+        self.i2c.write(self.address, &[IOCON_BANK1, 0x00])?;
+        self.i2c.write(self.address, &[GPPUA, buttons])?;
+        self.i2c.write(self.address, &[GPIOA, self.port_a()])?;
+        self.i2c.write(self.address, &[GPIOB, port_b])?;
+        self.i2c.write(self.address, &[IODIRA, buttons])?;
+        self.i2c.write(self.address, &[IODIRB, 0x00])?;
+        self.reading = false;
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransport for Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    type Error = I::Error;
+
+    fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        for levels in levels {
+            if levels.read != self.reading {
+                let iodir = if levels.read { DATA } else { 0x00 };
+                self.i2c.write(self.address, &[IODIRB, iodir])?;
+                self.reading = levels.read;
+            }
+            // Every level goes out in its own write, as a second byte would go to OLATA, the
+            // register after GPIOB.
+            self.i2c
+                .write(self.address, &[GPIOB, self.port_b(*levels)])?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> LcdNibbleTransportRead for Mcp23017Rgb<I>
+where
+    I: I2c,
+{
+    fn read(&mut self) -> Result<u8, Self::Error> {
+        let mut gpio = 0;
+        self.i2c
+            .write_read(self.address, &[GPIOB], core::slice::from_mut(&mut gpio))?;
+        Ok(Self::nibble(gpio))
+    }
+}
+
+impl<I, T> LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+{
+    /// Switches the red, green and blue LEDs of the backlight on or off.
+    pub fn set_backlight_rgb(&mut self, r: bool, g: bool, b: bool) -> Result<(), I::Error> {
+        let expander = self.bus.transport_mut();
+        expander.backlight = Mcp23017Rgb::<I>::backlight_bits(r, g, b);
+        expander
+            .i2c
+            .write(expander.address, &[GPIOA, expander.port_a()])?;
+        expander.write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+    }
+
+    /// The buttons that are currently pressed.
+    pub fn read_buttons(&mut self) -> Result<ShieldButtons, I::Error> {
+        let expander = self.bus.transport_mut();
+        let mut gpio = 0;
+        expander
+            .i2c
+            .write_read(expander.address, &[GPIOA], core::slice::from_mut(&mut gpio))?;
+        // The buttons pull their GPIO to ground.
+        Ok(ShieldButtons::from_bits_truncate(!gpio))
+    }
+}
+
+impl<I, T, Delay> LcdWrite<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = I::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write(rs, data, delay)
+    }
+}
+
+impl<I, T, Delay> LcdRead<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        self.bus.read_status(delay)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.bus.read_data(delay)
+    }
+}
+
+impl<I, T, Delay> LcdInit<Delay> for LcdMcp23017RgbBus<I, T>
+where
+    I: I2c,
+    T: LcdTimingsI2c<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.transport_mut().configure()?;
+        self.bus.init(function, display, entry, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use crate::{
+        bus::{
+            blocking::{LcdRead, LcdWrite},
+            LcdMcp23017RgbBus, LcdRegisterSelect,
+        },
+        mock::{MockI2c, NoDelay},
+    };
+
+    use super::{GPIOB, IODIRB};
+
+    #[test]
+    fn test_write_pulses_enable_in_separate_writes() {
+        let mut bus = LcdMcp23017RgbBus::new(MockI2c::new(), 0x20);
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        // RS is GPB7, E is GPB5 and D4 to D7 are GPB4 to GPB1.
+        assert_eq!(
+            vec![
+                vec![GPIOB, 0x84],
+                vec![GPIOB, 0xa4],
+                vec![GPIOB, 0x84],
+                vec![GPIOB, 0x90],
+                vec![GPIOB, 0xb0],
+                vec![GPIOB, 0x90],
+            ],
+            bus.destroy().writes(0x20)
+        );
+    }
+
+    #[test]
+    fn test_read_turns_data_lines_around() {
+        let mut i2c = MockI2c::new();
+        i2c.reads.extend([0x04, 0x10]);
+        let mut bus = LcdMcp23017RgbBus::new(i2c, 0x20);
+        assert_eq!(Ok(0x41), bus.read_data(&mut NoDelay));
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        // RW is GPB6, and the data lines are inputs until the next write.
+        assert_eq!(
+            vec![
+                vec![IODIRB, 0x1e],
+                vec![GPIOB, 0xc0],
+                vec![GPIOB, 0xe0],
+                vec![GPIOB],
+                vec![GPIOB, 0xc0],
+                vec![GPIOB, 0xc0],
+                vec![GPIOB, 0xe0],
+                vec![GPIOB],
+                vec![GPIOB, 0xc0],
+                vec![IODIRB, 0x00],
+                vec![GPIOB, 0x84],
+            ],
+            bus.destroy().writes(0x20)[..11]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use bitflags::bitflags;
+
+use crate::bus::timings::DefaultTimingsI2c;
+
+use super::{LcdNibbleBus, LcdNibbleLevels, LcdRegisterSelect};
+
+const IODIRA: u8 = 0x00;
+const IODIRB: u8 = 0x01;
+const GPPUA: u8 = 0x0c;
+const GPIOA: u8 = 0x12;
+const GPIOB: u8 = 0x13;
+
+/// The register addresses above assume IOCON.BANK = 0, the power-on default. An MCU reset
+/// doesn't reset the expander, so init clears IOCON through its BANK = 1 address first. With
+/// BANK = 0 already set, that address is GPINTENB, whose default is 0 as well.
+const IOCON_BANK1: u8 = 0x05;
+
+// Port A holds the buttons and the red and green LEDs, port B the LCD and the blue LED. The LEDs
+// are on while their GPIO is low.
+const RED: u8 = 0x40;
+const GREEN: u8 = 0x80;
+const BLUE: u8 = 0x01;
+const REGISTER_SELECT: u8 = 0x80;
+const READ: u8 = 0x40;
+const ENABLE: u8 = 0x20;
+/// D4 to D7 are connected to GPB4 to GPB1 in reverse order.
+const DATA: u8 = 0x1e;
+
+bitflags! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ShieldButtons: u8 {
+        const SELECT = 0x01;
+        const RIGHT = 0x02;
+        const DOWN = 0x04;
+        const UP = 0x08;
+        const LEFT = 0x10;
+    }
+}
+
+impl core::fmt::Debug for ShieldButtons {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("ShieldButtons")
+            .field("select", &self.contains(Self::SELECT))
+            .field("right", &self.contains(Self::RIGHT))
+            .field("down", &self.contains(Self::DOWN))
+            .field("up", &self.contains(Self::UP))
+            .field("left", &self.contains(Self::LEFT))
+            .finish()
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for ShieldButtons {
+    fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        fmt.debug_struct("ShieldButtons")?
+            .field("select", &self.contains(Self::SELECT))?
+            .field("right", &self.contains(Self::RIGHT))?
+            .field("down", &self.contains(Self::DOWN))?
+            .field("up", &self.contains(Self::UP))?
+            .field("left", &self.contains(Self::LEFT))?
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ShieldButtons {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "ShieldButtons {{ select: {}, right: {}, down: {}, up: {}, left: {} }}",
+            self.contains(Self::SELECT),
+            self.contains(Self::RIGHT),
+            self.contains(Self::DOWN),
+            self.contains(Self::UP),
+            self.contains(Self::LEFT)
+        )
+    }
+}
+
+/// The expander of a [`LcdMcp23017RgbBus`], which is the
+/// [`LcdNibbleTransport`](crate::bus::blocking::LcdNibbleTransport) of its [`LcdNibbleBus`].
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Mcp23017Rgb<I> {
+    i2c: I,
+    address: u8,
+    /// The LEDs that are on, as `RED | GREEN | BLUE`.
+    backlight: u8,
+    /// Whether the data lines are inputs, which they stay after a read until the next write.
+    reading: bool,
+}
+
+impl<I> Mcp23017Rgb<I> {
+    #[inline]
+    fn backlight_bits(r: bool, g: bool, b: bool) -> u8 {
+        (if r { RED } else { 0 }) | (if g { GREEN } else { 0 }) | (if b { BLUE } else { 0 })
+    }
+
+    /// The outputs of port A, which only drives the red and green LEDs.
+    #[inline]
+    fn port_a(&self) -> u8 {
+        !self.backlight & (RED | GREEN)
+    }
+
+    /// The outputs of port B for `levels`. While reading, the data lines are inputs and their
+    /// output latches are left low.
+    fn port_b(&self, levels: LcdNibbleLevels) -> u8 {
+        let mut outputs = !self.backlight & BLUE;
+        if levels.rs == LcdRegisterSelect::Memory {
+            outputs |= REGISTER_SELECT;
+        }
+        if levels.read {
+            outputs |= READ;
+        } else {
+            for i in 0..4 {
+                if levels.data & (1 << i) != 0 {
+                    outputs |= 0x10 >> i;
+                }
+            }
+        }
+        if levels.enable {
+            outputs |= ENABLE;
+        }
+        outputs
+    }
+
+    /// The nibble on the data lines of port B.
+    fn nibble(port_b: u8) -> u8 {
+        (0..4)
+            .filter(|i| port_b & (0x10 >> i) != 0)
+            .fold(0, |nibble, i| nibble | (1 << i))
+    }
+}
+
+/// The Adafruit RGB LCD shield and its clones, which connect an LCD in 4-bit mode, an RGB
+/// backlight and five buttons to an MCP23017.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdMcp23017RgbBus<I, T> {
+    bus: LcdNibbleBus<Mcp23017Rgb<I>, T>,
+}
+
+impl<I> LcdMcp23017RgbBus<I, DefaultTimingsI2c> {
+    /// The address of the shield unless its address jumpers are closed.
+    pub const DEFAULT_ADDRESS: u8 = 0x20;
+
+    #[inline]
+    pub fn new(i2c: I, address: u8) -> Self {
+        Self::new_with_timings(i2c, address, DefaultTimingsI2c)
+    }
+}
+
+impl<I, T> LcdMcp23017RgbBus<I, T> {
+    #[inline]
+    pub fn new_with_timings(i2c: I, address: u8, timings: T) -> Self {
+        let expander = Mcp23017Rgb {
+            i2c,
+            address,
+            backlight: RED | GREEN | BLUE,
+            reading: false,
+        };
+        Self {
+            bus: LcdNibbleBus::new_with_timings(expander, timings),
+        }
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        self.bus.timings()
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.bus.set_timings(timings);
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdMcp23017RgbBus<I, T2> {
+        LcdMcp23017RgbBus {
+            bus: self.bus.with_timings(timings),
+        }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> I {
+        self.bus.destroy().i2c
+    }
+}
@@ -1,10 +1,30 @@
+#[cfg(feature = "async")]
+pub mod asynch;
 #[cfg(feature = "blocking")]
 pub mod blocking;
 
+mod aip31068;
+pub use aip31068::*;
+mod busy_flag;
+pub use busy_flag::*;
+mod dual_controller;
+pub use dual_controller::*;
 mod i2c_8574;
 pub use i2c_8574::*;
+mod mcp23008;
+pub use mcp23008::*;
+mod mcp23017_rgb;
+pub use mcp23017_rgb::*;
+mod nibble;
+pub use nibble::*;
 mod parallel;
 pub use parallel::*;
+mod parallel_port;
+pub use parallel_port::*;
+mod shift_register;
+pub use shift_register::*;
+mod tca9548a;
+pub use tca9548a::*;
 
 mod timings;
 pub use timings::*;
@@ -24,3 +44,90 @@ impl From<LcdRegisterSelect> for PinState {
         Self::from(value == LcdRegisterSelect::Memory)
     }
 }
+
+/// The kind of instruction a write carries, which determines how long the controller is busy
+/// executing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdInstruction {
+    /// Clears the display, takes up to 1.52 ms.
+    ClearDisplay,
+    /// Returns the cursor home, takes up to 1.52 ms.
+    ReturnHome,
+    /// Any other instruction, takes up to 37 µs.
+    Command,
+    /// Writes to or reads from DDRAM or CGRAM, takes up to 41 µs.
+    Data,
+}
+
+impl LcdInstruction {
+    /// Decodes the instruction class of a byte written to a register.
+    pub const fn decode(rs: LcdRegisterSelect, data: u8) -> Self {
+        match (rs, data) {
+            (LcdRegisterSelect::Memory, _) => Self::Data,
+            (LcdRegisterSelect::Control, crate::CLEAR_DISPLAY) => Self::ClearDisplay,
+            (LcdRegisterSelect::Control, 0x02..=0x03) => Self::ReturnHome,
+            (LcdRegisterSelect::Control, _) => Self::Command,
+        }
+    }
+
+    /// The instruction whose timings apply when the enable line is released after a read.
+    #[inline(always)]
+    pub(crate) const fn read_pace(rs: LcdRegisterSelect, polling: bool) -> Self {
+        // Reading the DDRAM or CGRAM advances the address counter just like writing does.
+        match (polling, rs) {
+            (true, _) | (false, LcdRegisterSelect::Memory) => Self::Data,
+            (false, LcdRegisterSelect::Control) => Self::Command,
+        }
+    }
+}
+
+/// How long a write holds the bus after the enable pulse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LcdPace {
+    /// Waits out the execution time of the instruction.
+    Execute(LcdInstruction),
+    /// Waits only until the enable cycle is complete, for writes that are followed by busy flag
+    /// polls.
+    Cycle,
+}
+
+impl LcdPace {
+    /// The instruction whose enable pulse width applies.
+    #[inline(always)]
+    pub(crate) const fn pulse(self) -> LcdInstruction {
+        match self {
+            Self::Execute(instruction) => instruction,
+            Self::Cycle => LcdInstruction::Data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LcdInstruction, LcdRegisterSelect};
+
+    #[test]
+    fn test_decode_instruction() {
+        use LcdRegisterSelect::{Control, Memory};
+
+        assert_eq!(
+            LcdInstruction::ClearDisplay,
+            LcdInstruction::decode(Control, 0x01)
+        );
+        assert_eq!(
+            LcdInstruction::ReturnHome,
+            LcdInstruction::decode(Control, 0x02)
+        );
+        assert_eq!(
+            LcdInstruction::ReturnHome,
+            LcdInstruction::decode(Control, 0x03)
+        );
+        assert_eq!(
+            LcdInstruction::Command,
+            LcdInstruction::decode(Control, 0x80 | 0x40)
+        );
+        assert_eq!(LcdInstruction::Data, LcdInstruction::decode(Memory, 0x01));
+    }
+}
@@ -0,0 +1,266 @@
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{
+        asynch::{
+            LcdBusyFlag, LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite,
+        },
+        AsyncLcdTimingsNibble, AsyncLcdTimingsNibbleRead, LcdInstruction, LcdNibbleLevels, LcdPace,
+        LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::LcdNibbleBus;
+
+/// Clocks one nibble into the LCD, waiting as long as `pace` asks for.
+async fn write_nibble_async<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    levels: LcdNibbleLevels,
+    pace: LcdPace,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    transport.write(&[levels, levels.enabled()]).await?;
+    timings.enable_pulse_on(pace.pulse(), delay).await;
+    transport.write(&[levels]).await?;
+    match pace {
+        LcdPace::Execute(instruction) => timings.enable_pulse_off(instruction, delay).await,
+        // Holding E low as long as it was held high completes the cycle.
+        LcdPace::Cycle => timings.enable_pulse_on(pace.pulse(), delay).await,
+    }
+    Ok(())
+}
+
+/// Writes a byte in two nibbles, waiting as long as `pace` asks for.
+pub(crate) async fn write_byte_async<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    rs: LcdRegisterSelect,
+    pace: LcdPace,
+    data: u8,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    let upper = LcdNibbleLevels::write(rs, data >> 4);
+    write_nibble_async(transport, timings, upper, pace, delay).await?;
+    let lower = LcdNibbleLevels::write(rs, data);
+    write_nibble_async(transport, timings, lower, pace, delay).await
+}
+
+/// Reads a byte in two nibbles.
+async fn read_byte_async<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    rs: LcdRegisterSelect,
+    polling: bool,
+    delay: &mut Delay,
+) -> Result<u8, X::Error>
+where
+    X: LcdNibbleTransportRead,
+    T: AsyncLcdTimingsNibbleRead<Delay>,
+    Delay: ?Sized,
+{
+    let levels = LcdNibbleLevels::read(rs);
+    let pace = LcdInstruction::read_pace(rs, polling);
+    let mut data = 0;
+
+    for shift in [4, 0] {
+        transport.write(&[levels, levels.enabled()]).await?;
+        // Busy flag polls are paced like data accesses, which have no execution time.
+        if polling {
+            timings.enable_pulse_on(pace, delay).await;
+        } else {
+            timings.read_delay(delay).await;
+        }
+        data |= (transport.read().await? & 0x0f) << shift;
+        transport.write(&[levels]).await?;
+        timings.enable_pulse_off(pace, delay).await;
+    }
+
+    Ok(data)
+}
+
+/// Switches the LCD to 4-bit mode from any state and configures it.
+async fn init_async<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    function: LcdFunctionMode,
+    display: LcdDisplayMode,
+    entry: LcdEntryMode,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    timings.power_on_delay(delay).await;
+
+    let levels = LcdNibbleLevels::write(LcdRegisterSelect::Control, 0x3);
+
+    transport.write(&[levels, levels.enabled()]).await?;
+    timings
+        .enable_pulse_on(LcdInstruction::Command, delay)
+        .await;
+    transport.write(&[levels]).await?;
+    timings.first_init_delay(delay).await;
+
+    transport.write(&[levels.enabled()]).await?;
+    timings
+        .enable_pulse_on(LcdInstruction::Command, delay)
+        .await;
+    transport.write(&[levels]).await?;
+    timings.second_init_delay(delay).await;
+
+    transport.write(&[levels.enabled()]).await?;
+    timings
+        .enable_pulse_on(LcdInstruction::Command, delay)
+        .await;
+    transport.write(&[levels]).await?;
+    timings
+        .enable_pulse_off(LcdInstruction::Command, delay)
+        .await;
+
+    // set 4-bit bus
+    write_nibble_async(
+        transport,
+        timings,
+        LcdNibbleLevels::write(LcdRegisterSelect::Control, crate::FUNCTION_SET >> 4),
+        LcdPace::Execute(LcdInstruction::Command),
+        delay,
+    )
+    .await?;
+
+    let commands = [
+        crate::FUNCTION_SET
+            | function
+                .intersection(LcdFunctionMode::all().difference(LcdFunctionMode::DATA_LENGTH))
+                .bits(),
+        crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+        crate::CLEAR_DISPLAY,
+        crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+    ];
+    for command in commands {
+        let pace = LcdPace::Execute(LcdInstruction::decode(LcdRegisterSelect::Control, command));
+        write_byte_async(
+            transport,
+            timings,
+            LcdRegisterSelect::Control,
+            pace,
+            command,
+            delay,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+impl<X, T, Delay> LcdWrite<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransport,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = X::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        write_byte_async(&mut self.transport, &self.timings, rs, pace, data, delay).await
+    }
+}
+
+impl<X, T, Delay> LcdRead<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransportRead,
+    T: AsyncLcdTimingsNibbleRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let rs = LcdRegisterSelect::Control;
+        read_byte_async(&mut self.transport, &self.timings, rs, false, delay)
+            .await
+            .map(LcdStatus::from_bits_retain)
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        let rs = LcdRegisterSelect::Memory;
+        read_byte_async(&mut self.transport, &self.timings, rs, false, delay).await
+    }
+}
+
+impl<X, T, Delay> LcdBusyFlag<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransportRead,
+    T: AsyncLcdTimingsNibbleRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        write_byte_async(
+            &mut self.transport,
+            &self.timings,
+            rs,
+            LcdPace::Cycle,
+            data,
+            delay,
+        )
+        .await
+    }
+
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let rs = LcdRegisterSelect::Control;
+        read_byte_async(&mut self.transport, &self.timings, rs, true, delay)
+            .await
+            .map(|data| LcdStatus::from_bits_retain(data).busy())
+    }
+
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay).await;
+        self.timings.enable_pulse_off(instruction, delay).await;
+    }
+}
+
+impl<X, T, Delay> LcdInit<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransport,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        init_async(
+            &mut self.transport,
+            &self.timings,
+            function,
+            display,
+            entry,
+            delay,
+        )
+        .await
+    }
+}
@@ -0,0 +1,344 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    bus::{
+        blocking::{
+            LcdBusyFlag, LcdInit, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite,
+        },
+        LcdInstruction, LcdNibbleLevels, LcdPace, LcdRegisterSelect, LcdTimingsNibble,
+        LcdTimingsNibbleRead,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::LcdNibbleBus;
+
+/// Clocks one nibble into the LCD, waiting as long as `pace` asks for.
+fn write_nibble<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    levels: LcdNibbleLevels,
+    pace: LcdPace,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: LcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    transport.write(&[levels, levels.enabled()])?;
+    timings.enable_pulse_on(pace.pulse(), delay);
+    transport.write(&[levels])?;
+    match pace {
+        LcdPace::Execute(instruction) => timings.enable_pulse_off(instruction, delay),
+        // Holding E low as long as it was held high completes the cycle.
+        LcdPace::Cycle => timings.enable_pulse_on(pace.pulse(), delay),
+    }
+    Ok(())
+}
+
+/// Writes a byte in two nibbles, waiting as long as `pace` asks for.
+pub(crate) fn write_byte<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    rs: LcdRegisterSelect,
+    pace: LcdPace,
+    data: u8,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: LcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    let upper = LcdNibbleLevels::write(rs, data >> 4);
+    write_nibble(transport, timings, upper, pace, delay)?;
+    let lower = LcdNibbleLevels::write(rs, data);
+    write_nibble(transport, timings, lower, pace, delay)
+}
+
+/// Reads a byte in two nibbles.
+fn read_byte<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    rs: LcdRegisterSelect,
+    polling: bool,
+    delay: &mut Delay,
+) -> Result<u8, X::Error>
+where
+    X: LcdNibbleTransportRead,
+    T: LcdTimingsNibbleRead<Delay>,
+    Delay: ?Sized,
+{
+    let levels = LcdNibbleLevels::read(rs);
+    let pace = LcdInstruction::read_pace(rs, polling);
+    let mut data = 0;
+
+    for shift in [4, 0] {
+        transport.write(&[levels, levels.enabled()])?;
+        // Busy flag polls are paced like data accesses, which have no execution time.
+        if polling {
+            timings.enable_pulse_on(pace, delay);
+        } else {
+            timings.read_delay(delay);
+        }
+        data |= (transport.read()? & 0x0f) << shift;
+        transport.write(&[levels])?;
+        timings.enable_pulse_off(pace, delay);
+    }
+
+    Ok(data)
+}
+
+/// Switches the LCD to 4-bit mode from any state and configures it.
+fn init<X, T, Delay>(
+    transport: &mut X,
+    timings: &T,
+    function: LcdFunctionMode,
+    display: LcdDisplayMode,
+    entry: LcdEntryMode,
+    delay: &mut Delay,
+) -> Result<(), X::Error>
+where
+    X: LcdNibbleTransport,
+    T: LcdTimingsNibble<Delay>,
+    Delay: ?Sized,
+{
+    timings.power_on_delay(delay);
+
+    let levels = LcdNibbleLevels::write(LcdRegisterSelect::Control, 0x3);
+
+    transport.write(&[levels, levels.enabled()])?;
+    timings.enable_pulse_on(LcdInstruction::Command, delay);
+    transport.write(&[levels])?;
+    timings.first_init_delay(delay);
+
+    transport.write(&[levels.enabled()])?;
+    timings.enable_pulse_on(LcdInstruction::Command, delay);
+    transport.write(&[levels])?;
+    timings.second_init_delay(delay);
+
+    transport.write(&[levels.enabled()])?;
+    timings.enable_pulse_on(LcdInstruction::Command, delay);
+    transport.write(&[levels])?;
+    timings.enable_pulse_off(LcdInstruction::Command, delay);
+
+    // set 4-bit bus
+    write_nibble(
+        transport,
+        timings,
+        LcdNibbleLevels::write(LcdRegisterSelect::Control, crate::FUNCTION_SET >> 4),
+        LcdPace::Execute(LcdInstruction::Command),
+        delay,
+    )?;
+
+    let commands = [
+        crate::FUNCTION_SET
+            | function
+                .intersection(LcdFunctionMode::all().difference(LcdFunctionMode::DATA_LENGTH))
+                .bits(),
+        crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+        crate::CLEAR_DISPLAY,
+        crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+    ];
+    for command in commands {
+        let pace = LcdPace::Execute(LcdInstruction::decode(LcdRegisterSelect::Control, command));
+        write_byte(
+            transport,
+            timings,
+            LcdRegisterSelect::Control,
+            pace,
+            command,
+            delay,
+        )?;
+    }
+
+    Ok(())
+}
+
+impl<X, T, Delay> LcdWrite<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransport,
+    T: LcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = X::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        write_byte(&mut self.transport, &self.timings, rs, pace, data, delay)
+    }
+}
+
+impl<X, T, Delay> LcdRead<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransportRead,
+    T: LcdTimingsNibbleRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let rs = LcdRegisterSelect::Control;
+        read_byte(&mut self.transport, &self.timings, rs, false, delay)
+            .map(LcdStatus::from_bits_retain)
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        let rs = LcdRegisterSelect::Memory;
+        read_byte(&mut self.transport, &self.timings, rs, false, delay)
+    }
+}
+
+impl<X, T, Delay> LcdBusyFlag<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransportRead,
+    T: LcdTimingsNibbleRead<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        write_byte(
+            &mut self.transport,
+            &self.timings,
+            rs,
+            LcdPace::Cycle,
+            data,
+            delay,
+        )
+    }
+
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let rs = LcdRegisterSelect::Control;
+        read_byte(&mut self.transport, &self.timings, rs, true, delay)
+            .map(|data| LcdStatus::from_bits_retain(data).busy())
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay);
+        self.timings.enable_pulse_off(instruction, delay);
+    }
+}
+
+impl<X, T, Delay> LcdInit<Delay> for LcdNibbleBus<X, T>
+where
+    X: LcdNibbleTransport,
+    T: LcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        init(
+            &mut self.transport,
+            &self.timings,
+            function,
+            display,
+            entry,
+            delay,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{collections::VecDeque, vec, vec::Vec};
+
+    use crate::{
+        bus::{
+            blocking::{
+                LcdBusyFlag, LcdNibbleTransport, LcdNibbleTransportRead, LcdRead, LcdWrite,
+            },
+            LcdNibbleBus, LcdNibbleLevels, LcdRegisterSelect,
+        },
+        mock::{CountingDelay, NoDelay},
+    };
+
+    /// A transport that records every write and answers reads from a queue.
+    #[derive(Default)]
+    struct MockTransport {
+        writes: Vec<Vec<LcdNibbleLevels>>,
+        reads: VecDeque<u8>,
+    }
+
+    impl LcdNibbleTransport for MockTransport {
+        type Error = ();
+
+        fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+            self.writes.push(levels.to_vec());
+            Ok(())
+        }
+    }
+
+    impl LcdNibbleTransportRead for MockTransport {
+        fn read(&mut self) -> Result<u8, Self::Error> {
+            Ok(self.reads.pop_front().unwrap_or(0))
+        }
+    }
+
+    #[test]
+    fn test_write_pulses_each_nibble() {
+        let mut bus = LcdNibbleBus::new(MockTransport::default());
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        let upper = LcdNibbleLevels::write(LcdRegisterSelect::Memory, 0x4);
+        let lower = LcdNibbleLevels::write(LcdRegisterSelect::Memory, 0x1);
+        assert_eq!(
+            vec![
+                vec![upper, upper.enabled()],
+                vec![upper],
+                vec![lower, lower.enabled()],
+                vec![lower],
+            ],
+            bus.destroy().writes
+        );
+    }
+
+    #[test]
+    fn test_read_samples_upper_nibble_first() {
+        let transport = MockTransport {
+            reads: [0x8, 0x3].into(),
+            ..Default::default()
+        };
+        let mut bus = LcdNibbleBus::new(transport);
+        let status = bus.read_status(&mut NoDelay).unwrap();
+        assert!(status.busy());
+        assert_eq!(0x03, status.address());
+
+        let levels = LcdNibbleLevels::read(LcdRegisterSelect::Control);
+        assert_eq!(
+            vec![
+                vec![levels, levels.enabled()],
+                vec![levels],
+                vec![levels, levels.enabled()],
+                vec![levels],
+            ],
+            bus.destroy().writes
+        );
+    }
+
+    #[test]
+    fn test_write_no_wait_holds_only_the_enable_cycle() {
+        let mut bus = LcdNibbleBus::new(MockTransport::default());
+
+        let mut delay = CountingDelay::default();
+        bus.write_no_wait(LcdRegisterSelect::Control, 0x01, &mut delay)
+            .unwrap();
+        assert_eq!(12_000, delay.ns);
+        assert_eq!(4, bus.destroy().writes.len());
+    }
+}
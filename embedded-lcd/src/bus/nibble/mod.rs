@@ -0,0 +1,120 @@
+#[cfg(feature = "async")]
+pub(super) mod asynch;
+#[cfg(feature = "blocking")]
+pub(super) mod blocking;
+
+use crate::bus::timings::DefaultTimingsI2c;
+
+use super::LcdRegisterSelect;
+
+/// The levels of the signals of a 4-bit bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdNibbleLevels {
+    pub rs: LcdRegisterSelect,
+    /// RW is high, and the data lines are released so that the LCD can drive them.
+    pub read: bool,
+    pub enable: bool,
+    /// D4 to D7 in the lower nibble, ignored while reading.
+    pub data: u8,
+}
+
+impl LcdNibbleLevels {
+    /// The levels for writing `nibble`, with the enable line low.
+    #[inline]
+    pub const fn write(rs: LcdRegisterSelect, nibble: u8) -> Self {
+        Self {
+            rs,
+            read: false,
+            enable: false,
+            data: nibble & 0x0f,
+        }
+    }
+
+    /// The levels for reading, with the enable line low.
+    #[inline]
+    pub const fn read(rs: LcdRegisterSelect) -> Self {
+        Self {
+            rs,
+            read: true,
+            enable: false,
+            data: 0x0f,
+        }
+    }
+
+    /// The same levels with the enable line high.
+    #[inline]
+    pub const fn enabled(self) -> Self {
+        Self {
+            enable: true,
+            ..self
+        }
+    }
+}
+
+/// An LCD in 4-bit mode behind any [`LcdNibbleTransport`](crate::bus::blocking::LcdNibbleTransport),
+/// such as a GPIO expander.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdNibbleBus<X, T> {
+    transport: X,
+    timings: T,
+}
+
+impl<X> LcdNibbleBus<X, DefaultTimingsI2c> {
+    #[inline]
+    pub fn new(transport: X) -> Self {
+        Self::new_with_timings(transport, DefaultTimingsI2c)
+    }
+}
+
+impl<X, T> LcdNibbleBus<X, T> {
+    #[inline]
+    pub fn new_with_timings(transport: X, timings: T) -> Self {
+        Self { transport, timings }
+    }
+
+    #[inline]
+    pub fn transport(&self) -> &X {
+        &self.transport
+    }
+
+    /// Gives access to the transport, e.g. to switch a backlight on the same expander.
+    #[inline]
+    pub fn transport_mut(&mut self) -> &mut X {
+        &mut self.transport
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        &self.timings
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.timings = timings;
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdNibbleBus<X, T2> {
+        LcdNibbleBus {
+            transport: self.transport,
+            timings,
+        }
+    }
+
+    /// Borrows the transport and the timings at once, for buses that extend this one.
+    #[inline]
+    pub(crate) fn parts_mut(&mut self) -> (&mut X, &T) {
+        (&mut self.transport, &self.timings)
+    }
+
+    #[inline]
+    pub fn destroy(self) -> X {
+        self.transport
+    }
+}
@@ -0,0 +1,803 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{
+        asynch::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        AsyncLcdTimingsParallel, LcdInstruction, LcdPace, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{
+    sealed::{LcdParallelReadModeSet as _, LcdParallelWriteModeSet as _},
+    LcdBidirectionalPin, LcdParallelBus, LcdParallelError, LcdParallelPins, LcdParallelPins8,
+    LcdParallelReadModeSet, LcdParallelSignal, LcdParallelWriteModeSet,
+};
+
+/// Raises E and holds it for the pulse width of `instruction`.
+#[inline(always)]
+pub(crate) async fn enable_on_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    en.set_high()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.enable_pulse_on(instruction, delay).await;
+    Ok(())
+}
+
+/// Drops E and waits out the execution time of `instruction`.
+#[inline(always)]
+pub(crate) async fn enable_off_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.enable_pulse_off(instruction, delay).await;
+    Ok(())
+}
+
+/// Raises E and waits until the controller drives the data lines.
+#[inline(always)]
+pub(crate) async fn enable_on_read_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    polling: bool,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    en.set_high()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    // Busy flag polls are paced like data accesses, which have no execution time.
+    if polling {
+        timings.enable_pulse_on(LcdInstruction::Data, delay).await;
+    } else {
+        timings.read_delay(delay).await;
+    }
+    Ok(())
+}
+
+#[inline(always)]
+pub(crate) async fn enable_pulse_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    enable_on_async(en, timings, instruction, delay).await?;
+    enable_off_async(en, timings, instruction, delay).await
+}
+
+/// Pulses E and waits only until the enable cycle is complete, not for the instruction to
+/// execute.
+#[inline(always)]
+pub(crate) async fn enable_cycle_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    enable_on_async(en, timings, LcdInstruction::Data, delay).await?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    // Holding E low as long as it was held high completes the cycle.
+    timings.enable_pulse_on(LcdInstruction::Data, delay).await;
+    Ok(())
+}
+
+/// Pulses E and waits as long as `pace` asks for.
+#[inline(always)]
+pub(crate) async fn enable_paced_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    pace: LcdPace,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    match pace {
+        LcdPace::Execute(instruction) => enable_pulse_async(en, timings, instruction, delay).await,
+        LcdPace::Cycle => enable_cycle_async(en, timings, delay).await,
+    }
+}
+
+/// Latches the 8-bit function set that is already on the data lines three times, with the
+/// waits of the initialization by instruction in between.
+#[inline(always)]
+pub(crate) async fn wake_up_async<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: AsyncLcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    // The waits before the second and third pulse replace the execution time of the first two.
+    enable_on_async(en, timings, LcdInstruction::Command, delay).await?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.first_init_delay(delay).await;
+    enable_on_async(en, timings, LcdInstruction::Command, delay).await?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.second_init_delay(delay).await;
+    enable_pulse_async(en, timings, LcdInstruction::Command, delay).await
+}
+
+impl<P: LcdParallelPins8, T> LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+{
+    #[inline(always)]
+    async fn read_8bit_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: AsyncLcdTimingsParallel<Delay>,
+    {
+        self.pins
+            .rw()
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .d0()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))?;
+        self.pins
+            .d1()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))?;
+        self.pins
+            .d2()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))?;
+        self.pins
+            .d3()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))?;
+        self.pins
+            .d4()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+        enable_on_read_async(self.pins.en(), &self.timings, polling, delay).await?;
+
+        let mut data = 0u8;
+        data |= self
+            .pins
+            .d0()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))? as u8;
+        data |= (self
+            .pins
+            .d1()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))? as u8)
+            << 1;
+        data |= (self
+            .pins
+            .d2()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))? as u8)
+            << 2;
+        data |= (self
+            .pins
+            .d3()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))? as u8)
+            << 3;
+        data |= (self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8)
+            << 4;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 5;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 6;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 7;
+
+        enable_off_async(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )
+        .await?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .d0()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))?;
+        self.pins
+            .d1()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))?;
+        self.pins
+            .d2()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))?;
+        self.pins
+            .d3()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))?;
+        self.pins
+            .d4()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        Ok(data)
+    }
+}
+
+impl<P: LcdParallelPins, T> LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+{
+    #[inline(always)]
+    async fn read_4bit_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: AsyncLcdTimingsParallel<Delay>,
+    {
+        self.pins
+            .rw()
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .d4()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        enable_on_read_async(self.pins.en(), &self.timings, polling, delay).await?;
+
+        let mut data = 0u8;
+        data |= (self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8)
+            << 4;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 5;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 6;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 7;
+
+        enable_off_async(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )
+        .await?;
+        enable_on_read_async(self.pins.en(), &self.timings, polling, delay).await?;
+
+        data |= self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 1;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 2;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 3;
+
+        enable_off_async(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )
+        .await?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .d4()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        Ok(data)
+    }
+}
+
+impl<P: LcdParallelPins8, T, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    type Error = LcdParallelError;
+
+    #[inline(always)]
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.set_8bit(data)?;
+        enable_pulse_async(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::decode(rs, data),
+            delay,
+        )
+        .await
+    }
+}
+
+impl<P: LcdParallelPins8, T, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let data = self
+            .read_8bit_async(LcdRegisterSelect::Control, false, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data))
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_8bit_async(LcdRegisterSelect::Memory, false, delay)
+            .await
+    }
+}
+
+impl<P: LcdParallelPins8, T, Delay> LcdBusyFlag<Delay> for LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.set_8bit(data)?;
+        enable_cycle_async(self.pins.en(), &self.timings, delay).await
+    }
+
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self
+            .read_8bit_async(LcdRegisterSelect::Control, true, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay).await;
+        self.timings.enable_pulse_off(instruction, delay).await;
+    }
+}
+
+impl<P: LcdParallelPins8, T, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .en()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        self.timings.power_on_delay(delay).await;
+
+        self.set_8bit(0x30)?;
+        wake_up_async(self.pins.en(), &self.timings, delay).await?;
+
+        self.write_command(
+            crate::FUNCTION_SET
+                | function
+                    .intersection(LcdFunctionMode::all())
+                    .union(LcdFunctionMode::DATA_LENGTH)
+                    .bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(crate::CLEAR_DISPLAY, delay).await?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl<P: LcdParallelPins, T, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    type Error = LcdParallelError;
+
+    #[inline(always)]
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        let instruction = LcdInstruction::decode(rs, data);
+        self.set_4bit(data >> 4)?;
+        enable_pulse_async(self.pins.en(), &self.timings, instruction, delay).await?;
+        self.set_4bit(data)?;
+        enable_pulse_async(self.pins.en(), &self.timings, instruction, delay).await
+    }
+}
+
+impl<P: LcdParallelPins, T, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let data = self
+            .read_4bit_async(LcdRegisterSelect::Control, false, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data))
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_4bit_async(LcdRegisterSelect::Memory, false, delay)
+            .await
+    }
+}
+
+impl<P: LcdParallelPins, T, Delay> LcdBusyFlag<Delay> for LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.set_4bit(data >> 4)?;
+        enable_cycle_async(self.pins.en(), &self.timings, delay).await?;
+        self.set_4bit(data)?;
+        enable_cycle_async(self.pins.en(), &self.timings, delay).await
+    }
+
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self
+            .read_4bit_async(LcdRegisterSelect::Control, true, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay).await;
+        self.timings.enable_pulse_off(instruction, delay).await;
+    }
+}
+
+impl<P: LcdParallelPins, T, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn init(
+        &mut self,
+        function: crate::driver::LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: crate::driver::LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .en()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        self.timings.power_on_delay(delay).await;
+
+        self.set_4bit(0x3)?;
+        wake_up_async(self.pins.en(), &self.timings, delay).await?;
+
+        self.set_4bit(crate::FUNCTION_SET >> 4)?; // set 4-bit bus
+        enable_pulse_async(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::Command,
+            delay,
+        )
+        .await?;
+
+        self.write_command(
+            crate::FUNCTION_SET
+                | function
+                    .intersection(LcdFunctionMode::all().difference(LcdFunctionMode::DATA_LENGTH))
+                    .bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(crate::CLEAR_DISPLAY, delay).await?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -1,251 +1,476 @@
-use embedded_hal::{
-    delay::DelayNs,
-    digital::{ErrorType, InputPin, OutputPin, PinState},
-};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
 
 use crate::{
     bus::{
-        blocking::{LcdInit, LcdRead, LcdWrite},
-        LcdRegisterSelect, LcdTimingsParallel,
+        blocking::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        LcdInstruction, LcdPace, LcdRegisterSelect, LcdTimingsParallel,
     },
     LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
 };
 
 use super::{
     sealed::{LcdParallelReadModeSet as _, LcdParallelWriteModeSet as _},
-    LcdParallelBus, LcdParallelPins, LcdParallelReadModeSet, LcdParallelWriteModeSet,
+    LcdBidirectionalPin, LcdParallelBus, LcdParallelError, LcdParallelPins, LcdParallelPins8,
+    LcdParallelReadModeSet, LcdParallelSignal, LcdParallelWriteModeSet,
 };
 
-impl<P: LcdParallelPins, T, const WIDTH: u8> LcdParallelBus<P, T, WIDTH>
+/// Raises E and holds it for the pulse width of `instruction`.
+#[inline(always)]
+pub(crate) fn enable_on<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
 where
-    P::EN: OutputPin,
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
 {
-    #[inline(always)]
-    fn enable_on<Delay: ?Sized>(
-        &mut self,
-        rs: LcdRegisterSelect,
-        delay: &mut Delay,
-    ) -> Result<(), <P::EN as ErrorType>::Error>
-    where
-        T: LcdTimingsParallel<Delay>,
-    {
-        self.pins.en().set_high()?;
-        self.timings.enable_pulse_on(rs, delay);
-        Ok(())
-    }
-
-    #[inline(always)]
-    fn enable_off<Delay: ?Sized>(
-        &mut self,
-        rs: LcdRegisterSelect,
-        delay: &mut Delay,
-    ) -> Result<(), <P::EN as ErrorType>::Error>
-    where
-        T: LcdTimingsParallel<Delay>,
-    {
-        self.pins.en().set_low()?;
-        self.timings.enable_pulse_off(rs, delay);
-        Ok(())
-    }
+    en.set_high()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.enable_pulse_on(instruction, delay);
+    Ok(())
+}
 
-    #[inline(always)]
-    fn enable_on_read<Delay: ?Sized>(
-        &mut self,
-        delay: &mut Delay,
-    ) -> Result<(), <P::EN as ErrorType>::Error>
-    where
-        T: LcdTimingsParallel<Delay>,
-    {
-        self.pins.en().set_high()?;
-        self.timings.read_delay(delay);
-        Ok(())
-    }
+/// Drops E and waits out the execution time of `instruction`.
+#[inline(always)]
+pub(crate) fn enable_off<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.enable_pulse_off(instruction, delay);
+    Ok(())
+}
 
-    #[inline(always)]
-    fn enable_pulse<Delay: ?Sized>(
-        &mut self,
-        rs: LcdRegisterSelect,
-        delay: &mut Delay,
-    ) -> Result<(), <P::EN as ErrorType>::Error>
-    where
-        T: LcdTimingsParallel<Delay>,
-    {
-        self.enable_on(rs, delay)?;
-        self.enable_off(rs, delay)?;
-        Ok(())
+/// Raises E and waits until the controller drives the data lines.
+#[inline(always)]
+pub(crate) fn enable_on_read<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    polling: bool,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    en.set_high()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    // Busy flag polls are paced like data accesses, which have no execution time.
+    if polling {
+        timings.enable_pulse_on(LcdInstruction::Data, delay);
+    } else {
+        timings.read_delay(delay);
     }
+    Ok(())
+}
 
-    #[inline(always)]
-    fn enable_pulse_no_delay_after<Delay: ?Sized>(
-        &mut self,
-        rs: LcdRegisterSelect,
-        delay: &mut Delay,
-    ) -> Result<(), <P::EN as ErrorType>::Error>
-    where
-        T: LcdTimingsParallel<Delay>,
-    {
-        self.enable_on(rs, delay)?;
-        self.pins.en().set_low()?;
-        Ok(())
-    }
+#[inline(always)]
+pub(crate) fn enable_pulse<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    instruction: LcdInstruction,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    enable_on(en, timings, instruction, delay)?;
+    enable_off(en, timings, instruction, delay)
 }
 
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 8>
+/// Pulses E and waits only until the enable cycle is complete, not for the instruction to
+/// execute.
+#[inline(always)]
+pub(crate) fn enable_cycle<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
 where
-    P::RS: OutputPin<Error = E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E>,
-    P::D1: OutputPin<Error = E>,
-    P::D2: OutputPin<Error = E>,
-    P::D3: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
 {
-    #[inline(always)]
-    fn set_8bit(&mut self, data: u8) -> Result<(), E> {
-        self.pins.d0().set_state(PinState::from(data & 0x01 != 0))?;
-        self.pins.d1().set_state(PinState::from(data & 0x02 != 0))?;
-        self.pins.d2().set_state(PinState::from(data & 0x04 != 0))?;
-        self.pins.d3().set_state(PinState::from(data & 0x08 != 0))?;
-        self.pins.d4().set_state(PinState::from(data & 0x10 != 0))?;
-        self.pins.d5().set_state(PinState::from(data & 0x20 != 0))?;
-        self.pins.d6().set_state(PinState::from(data & 0x40 != 0))?;
-        self.pins.d7().set_state(PinState::from(data & 0x80 != 0))?;
+    enable_on(en, timings, LcdInstruction::Data, delay)?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    // Holding E low as long as it was held high completes the cycle.
+    timings.enable_pulse_on(LcdInstruction::Data, delay);
+    Ok(())
+}
 
-        Ok(())
+/// Pulses E and waits as long as `pace` asks for.
+#[inline(always)]
+pub(crate) fn enable_paced<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    pace: LcdPace,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    match pace {
+        LcdPace::Execute(instruction) => enable_pulse(en, timings, instruction, delay),
+        LcdPace::Cycle => enable_cycle(en, timings, delay),
     }
 }
 
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 8>
+/// Latches the 8-bit function set that is already on the data lines three times, with the
+/// waits of the initialization by instruction in between.
+#[inline(always)]
+pub(crate) fn wake_up<EN, T, Delay>(
+    en: &mut EN,
+    timings: &T,
+    delay: &mut Delay,
+) -> Result<(), LcdParallelError>
+where
+    EN: OutputPin,
+    T: LcdTimingsParallel<Delay>,
+    Delay: ?Sized,
+{
+    // The waits before the second and third pulse replace the execution time of the first two.
+    enable_on(en, timings, LcdInstruction::Command, delay)?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.first_init_delay(delay);
+    enable_on(en, timings, LcdInstruction::Command, delay)?;
+    en.set_low()
+        .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+    timings.second_init_delay(delay);
+    enable_pulse(en, timings, LcdInstruction::Command, delay)
+}
+
+impl<P: LcdParallelPins8, T> LcdParallelBus<P, T, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E> + LcdParallelReadModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D1: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D2: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D3: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D4: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D5: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D6: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D7: OutputPin<Error = E> + InputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
 {
     #[inline(always)]
-    fn read_8bit<Delay: ?Sized>(&mut self, delay: &mut Delay) -> Result<u8, E>
+    fn read_8bit<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
     where
         T: LcdTimingsParallel<Delay>,
     {
-        self.pins.rw().set_read_mode()?;
-        self.pins.rs().set_low()?;
-        self.pins.d0().set_high()?;
-        self.pins.d1().set_high()?;
-        self.pins.d2().set_high()?;
-        self.pins.d3().set_high()?;
-        self.pins.d4().set_high()?;
-        self.pins.d5().set_high()?;
-        self.pins.d6().set_high()?;
-        self.pins.d7().set_high()?;
-        self.enable_on_read(delay)?;
+        self.pins
+            .rw()
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .d0()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))?;
+        self.pins
+            .d1()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))?;
+        self.pins
+            .d2()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))?;
+        self.pins
+            .d3()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))?;
+        self.pins
+            .d4()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+        enable_on_read(self.pins.en(), &self.timings, polling, delay)?;
 
         let mut data = 0u8;
-        data |= self.pins.d0().is_high()? as u8;
-        data |= (self.pins.d1().is_high()? as u8) << 1;
-        data |= (self.pins.d2().is_high()? as u8) << 2;
-        data |= (self.pins.d3().is_high()? as u8) << 3;
-        data |= (self.pins.d4().is_high()? as u8) << 4;
-        data |= (self.pins.d5().is_high()? as u8) << 5;
-        data |= (self.pins.d6().is_high()? as u8) << 6;
-        data |= (self.pins.d7().is_high()? as u8) << 7;
-
-        self.enable_off(LcdRegisterSelect::Control, delay)?;
-        self.pins.rw().set_write_mode()?;
+        data |= self
+            .pins
+            .d0()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))? as u8;
+        data |= (self
+            .pins
+            .d1()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))? as u8)
+            << 1;
+        data |= (self
+            .pins
+            .d2()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))? as u8)
+            << 2;
+        data |= (self
+            .pins
+            .d3()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))? as u8)
+            << 3;
+        data |= (self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8)
+            << 4;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 5;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 6;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 7;
+
+        enable_off(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .d0()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))?;
+        self.pins
+            .d1()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))?;
+        self.pins
+            .d2()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))?;
+        self.pins
+            .d3()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))?;
+        self.pins
+            .d4()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
 
         Ok(data)
     }
 }
 
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 4>
-where
-    P::RS: OutputPin<Error = E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
-{
-    #[inline(always)]
-    fn set_4bit(&mut self, data: u8) -> Result<(), E> {
-        self.pins.d4().set_state(PinState::from(data & 0x1 != 0))?;
-        self.pins.d5().set_state(PinState::from(data & 0x2 != 0))?;
-        self.pins.d6().set_state(PinState::from(data & 0x4 != 0))?;
-        self.pins.d7().set_state(PinState::from(data & 0x8 != 0))?;
-
-        Ok(())
-    }
-}
-
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 4>
+impl<P: LcdParallelPins, T> LcdParallelBus<P, T, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E> + LcdParallelReadModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: InputPin<Error = E> + OutputPin<Error = E>,
-    P::D5: InputPin<Error = E> + OutputPin<Error = E>,
-    P::D6: InputPin<Error = E> + OutputPin<Error = E>,
-    P::D7: InputPin<Error = E> + OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
 {
     #[inline(always)]
-    fn read_4bit<Delay: ?Sized>(&mut self, delay: &mut Delay) -> Result<u8, E>
+    fn read_4bit<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
     where
         T: LcdTimingsParallel<Delay>,
     {
-        self.pins.rw().set_read_mode()?;
-        self.pins.d4().set_high()?;
-        self.pins.d5().set_high()?;
-        self.pins.d6().set_high()?;
-        self.pins.d7().set_high()?;
-
-        self.enable_on_read(delay)?;
+        self.pins
+            .rw()
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .d4()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_input()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        enable_on_read(self.pins.en(), &self.timings, polling, delay)?;
 
         let mut data = 0u8;
-        data |= (self.pins.d4().is_high()? as u8) << 4;
-        data |= (self.pins.d5().is_high()? as u8) << 5;
-        data |= (self.pins.d6().is_high()? as u8) << 6;
-        data |= (self.pins.d7().is_high()? as u8) << 7;
-
-        self.enable_off(LcdRegisterSelect::Control, delay)?;
-        self.enable_on_read(delay)?;
-
-        data |= self.pins.d4().is_high()? as u8;
-        data |= (self.pins.d5().is_high()? as u8) << 1;
-        data |= (self.pins.d6().is_high()? as u8) << 2;
-        data |= (self.pins.d7().is_high()? as u8) << 3;
-
-        self.enable_off(LcdRegisterSelect::Control, delay)?;
-        self.pins.rw().set_write_mode()?;
+        data |= (self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8)
+            << 4;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 5;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 6;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 7;
+
+        enable_off(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )?;
+        enable_on_read(self.pins.en(), &self.timings, polling, delay)?;
+
+        data |= self
+            .pins
+            .d4()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))? as u8;
+        data |= (self
+            .pins
+            .d5()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))? as u8)
+            << 1;
+        data |= (self
+            .pins
+            .d6()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))? as u8)
+            << 2;
+        data |= (self
+            .pins
+            .d7()
+            .is_high()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))? as u8)
+            << 3;
+
+        enable_off(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .d4()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_as_output()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
 
         Ok(data)
     }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 8>
+impl<P: LcdParallelPins8, T, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E>,
-    P::D1: OutputPin<Error = E>,
-    P::D2: OutputPin<Error = E>,
-    P::D3: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
-    type Error = E;
+    type Error = LcdParallelError;
 
     #[inline(always)]
     fn write(
@@ -254,48 +479,100 @@ where
         data: u8,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.pins.rs().set_state(rs.into())?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
         self.set_8bit(data)?;
-        self.enable_pulse(rs, delay)
+        enable_pulse(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::decode(rs, data),
+            delay,
+        )
     }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 8>
+impl<P: LcdParallelPins8, T, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E> + LcdParallelReadModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D1: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D2: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D3: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D4: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D5: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D6: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D7: OutputPin<Error = E> + InputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
     fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
-        self.pins.rs().set_low()?;
-        let data = self.read_8bit(delay)?;
+        let data = self.read_8bit(LcdRegisterSelect::Control, false, delay)?;
         Ok(LcdStatus::from_bits_retain(data))
     }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_8bit(LcdRegisterSelect::Memory, false, delay)
+    }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 8>
+impl<P: LcdParallelPins8, T, Delay> LcdBusyFlag<Delay> for LcdParallelBus<P, T, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E>,
-    P::D1: OutputPin<Error = E>,
-    P::D2: OutputPin<Error = E>,
-    P::D3: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin + LcdBidirectionalPin,
+    P::D1: OutputPin + LcdBidirectionalPin,
+    P::D2: OutputPin + LcdBidirectionalPin,
+    P::D3: OutputPin + LcdBidirectionalPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.set_8bit(data)?;
+        enable_cycle(self.pins.en(), &self.timings, delay)
+    }
+
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self.read_8bit(LcdRegisterSelect::Control, true, delay)?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay);
+        self.timings.enable_pulse_off(instruction, delay);
+    }
+}
+
+impl<P: LcdParallelPins8, T, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
@@ -306,17 +583,22 @@ where
         entry: LcdEntryMode,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.pins.rs().set_low()?;
-        self.pins.rw().set_write_mode()?;
-        self.pins.en().set_low()?;
+        self.pins
+            .rs()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .en()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
         self.timings.power_on_delay(delay);
 
         self.set_8bit(0x30)?;
-        self.enable_pulse_no_delay_after(LcdRegisterSelect::Control, delay)?;
-        self.timings.first_init_delay(delay);
-        self.enable_pulse_no_delay_after(LcdRegisterSelect::Control, delay)?;
-        self.timings.second_init_delay(delay);
-        self.enable_pulse(LcdRegisterSelect::Control, delay)?;
+        wake_up(self.pins.en(), &self.timings, delay)?;
 
         self.write_command(
             crate::FUNCTION_SET
@@ -340,18 +622,18 @@ where
     }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 4>
+impl<P: LcdParallelPins, T, Delay> LcdWrite<Delay> for LcdParallelBus<P, T, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
-    type Error = E;
+    type Error = LcdParallelError;
 
     #[inline(always)]
     fn write(
@@ -360,42 +642,88 @@ where
         data: u8,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.pins.rs().set_state(rs.into())?;
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        let instruction = LcdInstruction::decode(rs, data);
         self.set_4bit(data >> 4)?;
-        self.enable_pulse(rs, delay)?;
+        enable_pulse(self.pins.en(), &self.timings, instruction, delay)?;
         self.set_4bit(data)?;
-        self.enable_pulse(rs, delay)
+        enable_pulse(self.pins.en(), &self.timings, instruction, delay)
     }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 4>
+impl<P: LcdParallelPins, T, Delay> LcdRead<Delay> for LcdParallelBus<P, T, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E> + LcdParallelReadModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D5: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D6: OutputPin<Error = E> + InputPin<Error = E>,
-    P::D7: OutputPin<Error = E> + InputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
     fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
-        self.pins.rs().set_low()?;
-        let data = self.read_4bit(delay)?;
+        let data = self.read_4bit(LcdRegisterSelect::Control, false, delay)?;
         Ok(LcdStatus::from_bits_retain(data))
     }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_4bit(LcdRegisterSelect::Memory, false, delay)
+    }
+}
+
+impl<P: LcdParallelPins, T, Delay> LcdBusyFlag<Delay> for LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin + LcdBidirectionalPin,
+    P::D5: OutputPin + LcdBidirectionalPin,
+    P::D6: OutputPin + LcdBidirectionalPin,
+    P::D7: OutputPin + LcdBidirectionalPin,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.pins
+            .rs()
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.set_4bit(data >> 4)?;
+        enable_cycle(self.pins.en(), &self.timings, delay)?;
+        self.set_4bit(data)?;
+        enable_cycle(self.pins.en(), &self.timings, delay)
+    }
+
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self.read_4bit(LcdRegisterSelect::Control, true, delay)?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay);
+        self.timings.enable_pulse_off(instruction, delay);
+    }
 }
 
-impl<P: LcdParallelPins, T, E, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 4>
+impl<P: LcdParallelPins, T, Delay> LcdInit<Delay> for LcdParallelBus<P, T, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
     Delay: DelayNs + ?Sized,
     T: LcdTimingsParallel<Delay>,
 {
@@ -406,20 +734,30 @@ where
         entry: crate::driver::LcdEntryMode,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.pins.rs().set_low()?;
-        self.pins.rw().set_write_mode()?;
-        self.pins.en().set_low()?;
+        self.pins
+            .rs()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.pins
+            .rw()
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.pins
+            .en()
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
         self.timings.power_on_delay(delay);
 
         self.set_4bit(0x3)?;
-        self.enable_pulse_no_delay_after(LcdRegisterSelect::Control, delay)?;
-        self.timings.first_init_delay(delay);
-        self.enable_pulse_no_delay_after(LcdRegisterSelect::Control, delay)?;
-        self.timings.second_init_delay(delay);
-        self.enable_pulse(LcdRegisterSelect::Control, delay)?;
+        wake_up(self.pins.en(), &self.timings, delay)?;
 
         self.set_4bit(crate::FUNCTION_SET >> 4)?; // set 4-bit bus
-        self.enable_pulse(LcdRegisterSelect::Control, delay)?;
+        enable_pulse(
+            self.pins.en(),
+            &self.timings,
+            LcdInstruction::Command,
+            delay,
+        )?;
 
         self.write_command(
             crate::FUNCTION_SET
@@ -441,3 +779,168 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{format, vec};
+
+    use embedded_hal::digital::ErrorKind;
+
+    use crate::{
+        bus::{
+            blocking::{LcdBusyFlag, LcdRead, LcdWrite},
+            LcdParallelBus, LcdParallelError, LcdParallelPinsRW4, LcdParallelPinsW4,
+            LcdParallelSignal, LcdRegisterSelect,
+        },
+        mock::{CountingDelay, MockPin, NoDelay, PinLog},
+    };
+
+    fn pins(
+        log: &PinLog,
+    ) -> LcdParallelPinsW4<MockPin, MockPin, MockPin, MockPin, MockPin, MockPin> {
+        LcdParallelPinsW4 {
+            rs: MockPin::new("RS", log),
+            en: MockPin::new("E", log),
+            d4: MockPin::new("D4", log),
+            d5: MockPin::new("D5", log),
+            d6: MockPin::new("D6", log),
+            d7: MockPin::new("D7", log),
+        }
+    }
+
+    #[test]
+    fn test_write_4bit() {
+        let log = PinLog::default();
+        let mut bus = LcdParallelBus::new_4bit(pins(&log));
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                ("RS", true),
+                ("D4", false),
+                ("D5", false),
+                ("D6", true),
+                ("D7", false),
+                ("E", true),
+                ("E", false),
+                ("D4", true),
+                ("D5", false),
+                ("D6", false),
+                ("D7", false),
+                ("E", true),
+                ("E", false),
+            ],
+            *log.borrow()
+        );
+    }
+
+    #[test]
+    fn test_error_names_the_failing_pin() {
+        let log = PinLog::default();
+        let mut pins = pins(&log);
+        pins.d6 = MockPin::new("D6", &log).failing();
+        let mut bus = LcdParallelBus::new_4bit(pins);
+
+        let error = bus
+            .write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap_err();
+        assert_eq!(
+            LcdParallelError {
+                signal: LcdParallelSignal::Data(6),
+                kind: ErrorKind::Other,
+            },
+            error
+        );
+        assert!(format!("{error}").starts_with("the D6 pin failed"));
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_error_udebug_names_the_kind() {
+        let error = LcdParallelError {
+            signal: LcdParallelSignal::Data(6),
+            kind: ErrorKind::Other,
+        };
+        let mut output = crate::mock::UString::default();
+        ufmt::uwrite!(output, "{:?}", error).unwrap();
+        assert_eq!(
+            "LcdParallelError { signal: Data(6), kind: Other }",
+            output.0
+        );
+    }
+
+    #[test]
+    fn test_error_names_the_enable_pin() {
+        let log = PinLog::default();
+        let mut pins = pins(&log);
+        pins.en = MockPin::new("E", &log).failing();
+        let mut bus = LcdParallelBus::new_4bit(pins);
+
+        let error = bus
+            .write(LcdRegisterSelect::Control, 0x01, &mut NoDelay)
+            .unwrap_err();
+        assert_eq!(LcdParallelSignal::Enable, error.signal);
+    }
+
+    #[test]
+    fn test_read_releases_data_pins() {
+        let log = PinLog::default();
+        let data = |name, high| {
+            let mut pin = MockPin::new(name, &log);
+            pin.input = high;
+            pin
+        };
+        let mut bus = LcdParallelBus::new_4bit(LcdParallelPinsRW4 {
+            rs: MockPin::new("RS", &log),
+            rw: MockPin::new("RW", &log),
+            en: MockPin::new("E", &log),
+            d4: data("D4", true),
+            d5: data("D5", false),
+            d6: data("D6", true),
+            d7: data("D7", false),
+        });
+
+        assert_eq!(0x55, bus.read_data(&mut NoDelay).unwrap());
+        assert_eq!(
+            vec![
+                ("RW", true),
+                ("RS", true),
+                ("D4", true),
+                ("D5", true),
+                ("D6", true),
+                ("D7", true),
+                ("E", true),
+            ],
+            log.borrow()[..7]
+        );
+    }
+
+    #[test]
+    fn test_write_no_wait_holds_only_the_enable_cycle() {
+        let log = PinLog::default();
+        let data = |name| MockPin::new(name, &log);
+        let mut bus = LcdParallelBus::new_4bit(LcdParallelPinsRW4 {
+            rs: MockPin::new("RS", &log),
+            rw: MockPin::new("RW", &log),
+            en: MockPin::new("E", &log),
+            d4: data("D4"),
+            d5: data("D5"),
+            d6: data("D6"),
+            d7: data("D7"),
+        });
+
+        // Two nibbles, each with E held high and then low for 3 µs.
+        let mut delay = CountingDelay::default();
+        bus.write_no_wait(LcdRegisterSelect::Memory, 0x41, &mut delay)
+            .unwrap();
+        assert_eq!(12_000, delay.ns);
+
+        let mut delay = CountingDelay::default();
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut delay)
+            .unwrap();
+        assert_eq!(106_000, delay.ns);
+    }
+}
@@ -1,28 +1,41 @@
+#[cfg(feature = "async")]
+pub(super) mod asynch;
 #[cfg(feature = "blocking")]
-mod blocking;
+pub(super) mod blocking;
 mod pins;
 pub use pins::*;
+mod shared;
+pub use shared::*;
 
-use embedded_hal::digital::OutputPin;
+#[cfg(feature = "derive")]
+pub use embedded_lcd_derive::LcdParallelPins;
 
-use crate::bus::timings;
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin, OutputPin, PinState};
+
+use crate::bus::{timings, LcdController, LcdEnableSelect};
 
 mod sealed {
+    use embedded_hal::digital::ErrorType;
+
     #[doc(hidden)]
-    pub trait LcdParallelWriteModeSet<E> {
-        fn set_write_mode(&mut self) -> Result<(), E>;
+    pub trait LcdParallelWriteModeSet: ErrorType {
+        fn set_write_mode(&mut self) -> Result<(), Self::Error>;
     }
 
     #[doc(hidden)]
-    pub trait LcdParallelReadModeSet<E> {
-        fn set_read_mode(&mut self) -> Result<(), E>;
+    pub trait LcdParallelReadModeSet: ErrorType {
+        fn set_read_mode(&mut self) -> Result<(), Self::Error>;
     }
 }
 
-pub trait LcdParallelWriteModeSet<E>: sealed::LcdParallelWriteModeSet<E> {}
+pub trait LcdParallelWriteModeSet: sealed::LcdParallelWriteModeSet {}
 
-pub trait LcdParallelReadModeSet<E>: sealed::LcdParallelReadModeSet<E> {}
+pub trait LcdParallelReadModeSet: sealed::LcdParallelReadModeSet {}
 
+/// Stands in for the RW pin when it is tied to ground.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct LcdParallelWriteOnly;
 
 impl LcdParallelWriteOnly {
@@ -38,29 +51,184 @@ impl LcdParallelWriteOnly {
     }
 }
 
-impl<E> sealed::LcdParallelWriteModeSet<E> for LcdParallelWriteOnly {
-    fn set_write_mode(&mut self) -> Result<(), E> {
+impl ErrorType for LcdParallelWriteOnly {
+    type Error = core::convert::Infallible;
+}
+
+impl sealed::LcdParallelWriteModeSet for LcdParallelWriteOnly {
+    fn set_write_mode(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
 }
 
-impl<E> LcdParallelWriteModeSet<E> for LcdParallelWriteOnly {}
+impl LcdParallelWriteModeSet for LcdParallelWriteOnly {}
 
-impl<T: OutputPin> sealed::LcdParallelWriteModeSet<T::Error> for T {
+impl<T: OutputPin> sealed::LcdParallelWriteModeSet for T {
     fn set_write_mode(&mut self) -> Result<(), T::Error> {
         self.set_low()
     }
 }
 
-impl<T: OutputPin> LcdParallelWriteModeSet<T::Error> for T {}
+impl<T: OutputPin> LcdParallelWriteModeSet for T {}
 
-impl<T: OutputPin> sealed::LcdParallelReadModeSet<T::Error> for T {
+impl<T: OutputPin> sealed::LcdParallelReadModeSet for T {
     fn set_read_mode(&mut self) -> Result<(), T::Error> {
         self.set_high()
     }
 }
 
-impl<T: OutputPin> LcdParallelReadModeSet<T::Error> for T {}
+impl<T: OutputPin> LcdParallelReadModeSet for T {}
+
+/// A line of a parallel bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdParallelSignal {
+    RegisterSelect,
+    ReadWrite,
+    Enable,
+    /// One of the data lines D0 to D7.
+    Data(u8),
+    /// The data lines of a [`LcdParallelPort`](crate::bus::LcdParallelPort).
+    DataPort,
+}
+
+impl core::fmt::Display for LcdParallelSignal {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::RegisterSelect => fmt.write_str("RS"),
+            Self::ReadWrite => fmt.write_str("RW"),
+            Self::Enable => fmt.write_str("E"),
+            Self::Data(line) => write!(fmt, "D{line}"),
+            Self::DataPort => fmt.write_str("data port"),
+        }
+    }
+}
+
+/// An error of a pin of a parallel bus, which may each have their own error type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LcdParallelError {
+    /// The line whose pin failed.
+    pub signal: LcdParallelSignal,
+    pub kind: ErrorKind,
+}
+
+impl LcdParallelError {
+    /// Returns a closure for `map_err` that records the failing `signal`.
+    #[inline(always)]
+    pub(crate) fn on<E: embedded_hal::digital::Error>(
+        signal: LcdParallelSignal,
+    ) -> impl FnOnce(E) -> Self {
+        move |source| Self {
+            signal,
+            kind: source.kind(),
+        }
+    }
+}
+
+impl core::fmt::Display for LcdParallelError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "the {} pin failed: {}", self.signal, self.kind)
+    }
+}
+
+impl core::error::Error for LcdParallelError {}
+
+impl embedded_hal::digital::Error for LcdParallelError {
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for LcdParallelError {
+    fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        fmt.debug_struct("LcdParallelError")?
+            .field("signal", &self.signal)?
+            .field("kind", &KindDebug(self.kind))?
+            .finish()
+    }
+}
+
+/// Shows an [`ErrorKind`] through its `Debug` impl, which names every kind.
+#[cfg(feature = "ufmt")]
+struct KindDebug(ErrorKind);
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for KindDebug {
+    fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        /// Passes `core::fmt` output on to the ufmt writer and keeps its error.
+        struct Adapter<'a, 'b, W: ufmt::uWrite + ?Sized> {
+            fmt: &'a mut ufmt::Formatter<'b, W>,
+            error: Option<W::Error>,
+        }
+
+        impl<W: ufmt::uWrite + ?Sized> core::fmt::Write for Adapter<'_, '_, W> {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.fmt.write_str(s).map_err(|error| {
+                    self.error = Some(error);
+                    core::fmt::Error
+                })
+            }
+        }
+
+        let mut adapter = Adapter { fmt, error: None };
+        let _ = core::fmt::Write::write_fmt(&mut adapter, format_args!("{:?}", self.0));
+        adapter.error.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for LcdParallelError {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "LcdParallelError {{ signal: {}, kind: {} }}",
+            self.signal,
+            self.kind
+        )
+    }
+}
+
+/// A data pin that is turned around to read from the LCD.
+///
+/// Pins that implement both [`InputPin`] and [`OutputPin`], such as open-drain pins, are
+/// released by driving them high. Push-pull pins that need a mode change can implement this
+/// trait instead of [`InputPin`].
+pub trait LcdBidirectionalPin: ErrorType {
+    /// Stops driving the pin, so that the LCD can drive it.
+    fn set_as_input(&mut self) -> Result<(), Self::Error>;
+
+    /// Drives the pin again after a read.
+    fn set_as_output(&mut self) -> Result<(), Self::Error>;
+
+    /// Samples the pin while it is an input.
+    fn is_high(&mut self) -> Result<bool, Self::Error>;
+}
+
+impl<T: InputPin + OutputPin> LcdBidirectionalPin for T {
+    #[inline(always)]
+    fn set_as_input(&mut self) -> Result<(), Self::Error> {
+        self.set_high()
+    }
+
+    #[inline(always)]
+    fn set_as_output(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        InputPin::is_high(self)
+    }
+}
 
 #[derive(Debug)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
@@ -70,19 +238,19 @@ pub struct LcdParallelBus<P: LcdParallelPins, T, const WIDTH: u8> {
     timings: T,
 }
 
-impl<P: LcdParallelPins, E> LcdParallelBus<P, timings::DefaultTimingsParallel8, 8>
+impl<P: LcdParallelPins8> LcdParallelBus<P, timings::DefaultTimingsParallel8, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E>,
-    P::D1: OutputPin<Error = E>,
-    P::D2: OutputPin<Error = E>,
-    P::D3: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
 {
     #[inline]
     pub fn new_8bit(pins: P) -> Self {
@@ -93,19 +261,19 @@ where
     }
 }
 
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 8>
+impl<P: LcdParallelPins8, T> LcdParallelBus<P, T, 8>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D0: OutputPin<Error = E>,
-    P::D1: OutputPin<Error = E>,
-    P::D2: OutputPin<Error = E>,
-    P::D3: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
 {
     #[inline]
     pub fn new_8bit_with_timings<Delay: ?Sized>(pins: P, timings: T) -> Self
@@ -116,16 +284,15 @@ where
     }
 }
 
-impl<P: LcdParallelPins<D0 = NoPin, D1 = NoPin, D2 = NoPin, D3 = NoPin>, E>
-    LcdParallelBus<P, timings::DefaultTimingsParallel4, 4>
+impl<P: LcdParallelPins> LcdParallelBus<P, timings::DefaultTimingsParallel4, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
 {
     #[inline]
     pub fn new_4bit(pins: P) -> Self {
@@ -136,15 +303,15 @@ where
     }
 }
 
-impl<P: LcdParallelPins, T, E> LcdParallelBus<P, T, 4>
+impl<P: LcdParallelPins, T> LcdParallelBus<P, T, 4>
 where
-    P::RS: OutputPin<Error = E>,
-    P::RW: LcdParallelWriteModeSet<E>,
-    P::EN: OutputPin<Error = E>,
-    P::D4: OutputPin<Error = E>,
-    P::D5: OutputPin<Error = E>,
-    P::D6: OutputPin<Error = E>,
-    P::D7: OutputPin<Error = E>,
+    P::RS: OutputPin,
+    P::RW: LcdParallelWriteModeSet,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
 {
     #[inline]
     pub fn new_4bit_with_timings<Delay: ?Sized>(pins: P, timings: T) -> Self
@@ -155,7 +322,122 @@ where
     }
 }
 
+impl<P: LcdParallelPins8, T> LcdParallelBus<P, T, 8>
+where
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D0: OutputPin,
+    P::D1: OutputPin,
+    P::D2: OutputPin,
+    P::D3: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+{
+    #[inline(always)]
+    fn set_8bit(&mut self, data: u8) -> Result<(), LcdParallelError> {
+        self.pins
+            .d0()
+            .set_state(PinState::from(data & 0x01 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(0)))?;
+        self.pins
+            .d1()
+            .set_state(PinState::from(data & 0x02 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(1)))?;
+        self.pins
+            .d2()
+            .set_state(PinState::from(data & 0x04 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(2)))?;
+        self.pins
+            .d3()
+            .set_state(PinState::from(data & 0x08 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(3)))?;
+        self.pins
+            .d4()
+            .set_state(PinState::from(data & 0x10 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_state(PinState::from(data & 0x20 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_state(PinState::from(data & 0x40 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_state(PinState::from(data & 0x80 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        Ok(())
+    }
+}
+
+impl<P: LcdParallelPins, T> LcdParallelBus<P, T, 4>
+where
+    P::RS: OutputPin,
+    P::EN: OutputPin,
+    P::D4: OutputPin,
+    P::D5: OutputPin,
+    P::D6: OutputPin,
+    P::D7: OutputPin,
+{
+    #[inline(always)]
+    fn set_4bit(&mut self, data: u8) -> Result<(), LcdParallelError> {
+        self.pins
+            .d4()
+            .set_state(PinState::from(data & 0x1 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(4)))?;
+        self.pins
+            .d5()
+            .set_state(PinState::from(data & 0x2 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(5)))?;
+        self.pins
+            .d6()
+            .set_state(PinState::from(data & 0x4 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(6)))?;
+        self.pins
+            .d7()
+            .set_state(PinState::from(data & 0x8 != 0))
+            .map_err(LcdParallelError::on(LcdParallelSignal::Data(7)))?;
+
+        Ok(())
+    }
+}
+
+impl<P, T, const WIDTH: u8> LcdEnableSelect for LcdParallelBus<P, T, WIDTH>
+where
+    P: LcdParallelPins,
+    P::EN: LcdEnableSelect,
+{
+    #[inline]
+    fn select(&mut self, controller: LcdController) {
+        self.pins.en().select(controller);
+    }
+}
+
 impl<P: LcdParallelPins, T, const WIDTH: u8> LcdParallelBus<P, T, WIDTH> {
+    #[inline]
+    pub fn timings(&self) -> &T {
+        &self.timings
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.timings = timings;
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdParallelBus<P, T2, WIDTH> {
+        LcdParallelBus {
+            pins: self.pins,
+            timings,
+        }
+    }
+
     pub fn destroy(self) -> P {
         self.pins
     }
@@ -1,13 +1,13 @@
 use super::LcdParallelWriteOnly;
 
+/// The pins of a parallel bus in 4-bit mode, or of the upper half of an 8-bit bus.
+///
+/// With the `derive` feature, `#[derive(LcdParallelPins)]` implements this and
+/// [`LcdParallelPins8`] from field attributes.
 pub trait LcdParallelPins {
     type RS;
     type RW;
     type EN;
-    type D0;
-    type D1;
-    type D2;
-    type D3;
     type D4;
     type D5;
     type D6;
@@ -16,21 +16,24 @@ pub trait LcdParallelPins {
     fn rs(&mut self) -> &mut Self::RS;
     fn rw(&mut self) -> &mut Self::RW;
     fn en(&mut self) -> &mut Self::EN;
-    fn d0(&mut self) -> &mut Self::D0;
-    fn d1(&mut self) -> &mut Self::D1;
-    fn d2(&mut self) -> &mut Self::D2;
-    fn d3(&mut self) -> &mut Self::D3;
     fn d4(&mut self) -> &mut Self::D4;
     fn d5(&mut self) -> &mut Self::D5;
     fn d6(&mut self) -> &mut Self::D6;
     fn d7(&mut self) -> &mut Self::D7;
 }
 
-/// Indicates that a pin does not exist.
-#[derive(Debug)]
-#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub enum NoPin {}
+/// The lower data pins of a parallel bus in 8-bit mode.
+pub trait LcdParallelPins8: LcdParallelPins {
+    type D0;
+    type D1;
+    type D2;
+    type D3;
+
+    fn d0(&mut self) -> &mut Self::D0;
+    fn d1(&mut self) -> &mut Self::D1;
+    fn d2(&mut self) -> &mut Self::D2;
+    fn d3(&mut self) -> &mut Self::D3;
+}
 
 #[derive(Debug)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
@@ -55,10 +58,6 @@ impl<RS, RW, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     type RS = RS;
     type RW = RW;
     type EN = EN;
-    type D0 = D0;
-    type D1 = D1;
-    type D2 = D2;
-    type D3 = D3;
     type D4 = D4;
     type D5 = D5;
     type D6 = D6;
@@ -73,18 +72,6 @@ impl<RS, RW, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     fn en(&mut self) -> &mut Self::EN {
         &mut self.en
     }
-    fn d0(&mut self) -> &mut Self::D0 {
-        &mut self.d0
-    }
-    fn d1(&mut self) -> &mut Self::D1 {
-        &mut self.d1
-    }
-    fn d2(&mut self) -> &mut Self::D2 {
-        &mut self.d2
-    }
-    fn d3(&mut self) -> &mut Self::D3 {
-        &mut self.d3
-    }
     fn d4(&mut self) -> &mut Self::D4 {
         &mut self.d4
     }
@@ -99,6 +86,28 @@ impl<RS, RW, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     }
 }
 
+impl<RS, RW, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins8
+    for LcdParallelPinsRW8<RS, RW, EN, D0, D1, D2, D3, D4, D5, D6, D7>
+{
+    type D0 = D0;
+    type D1 = D1;
+    type D2 = D2;
+    type D3 = D3;
+
+    fn d0(&mut self) -> &mut Self::D0 {
+        &mut self.d0
+    }
+    fn d1(&mut self) -> &mut Self::D1 {
+        &mut self.d1
+    }
+    fn d2(&mut self) -> &mut Self::D2 {
+        &mut self.d2
+    }
+    fn d3(&mut self) -> &mut Self::D3 {
+        &mut self.d3
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -121,10 +130,6 @@ impl<RS, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     type RS = RS;
     type RW = LcdParallelWriteOnly;
     type EN = EN;
-    type D0 = D0;
-    type D1 = D1;
-    type D2 = D2;
-    type D3 = D3;
     type D4 = D4;
     type D5 = D5;
     type D6 = D6;
@@ -139,18 +144,6 @@ impl<RS, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     fn en(&mut self) -> &mut Self::EN {
         &mut self.en
     }
-    fn d0(&mut self) -> &mut Self::D0 {
-        &mut self.d0
-    }
-    fn d1(&mut self) -> &mut Self::D1 {
-        &mut self.d1
-    }
-    fn d2(&mut self) -> &mut Self::D2 {
-        &mut self.d2
-    }
-    fn d3(&mut self) -> &mut Self::D3 {
-        &mut self.d3
-    }
     fn d4(&mut self) -> &mut Self::D4 {
         &mut self.d4
     }
@@ -165,6 +158,28 @@ impl<RS, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins
     }
 }
 
+impl<RS, EN, D0, D1, D2, D3, D4, D5, D6, D7> LcdParallelPins8
+    for LcdParallelPinsW8<RS, EN, D0, D1, D2, D3, D4, D5, D6, D7>
+{
+    type D0 = D0;
+    type D1 = D1;
+    type D2 = D2;
+    type D3 = D3;
+
+    fn d0(&mut self) -> &mut Self::D0 {
+        &mut self.d0
+    }
+    fn d1(&mut self) -> &mut Self::D1 {
+        &mut self.d1
+    }
+    fn d2(&mut self) -> &mut Self::D2 {
+        &mut self.d2
+    }
+    fn d3(&mut self) -> &mut Self::D3 {
+        &mut self.d3
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -184,10 +199,6 @@ impl<RS, RW, EN, D4, D5, D6, D7> LcdParallelPins
     type RS = RS;
     type RW = RW;
     type EN = EN;
-    type D0 = NoPin;
-    type D1 = NoPin;
-    type D2 = NoPin;
-    type D3 = NoPin;
     type D4 = D4;
     type D5 = D5;
     type D6 = D6;
@@ -202,18 +213,6 @@ impl<RS, RW, EN, D4, D5, D6, D7> LcdParallelPins
     fn en(&mut self) -> &mut Self::EN {
         &mut self.en
     }
-    fn d0(&mut self) -> &mut Self::D0 {
-        panic!()
-    }
-    fn d1(&mut self) -> &mut Self::D1 {
-        panic!()
-    }
-    fn d2(&mut self) -> &mut Self::D2 {
-        panic!()
-    }
-    fn d3(&mut self) -> &mut Self::D3 {
-        panic!()
-    }
     fn d4(&mut self) -> &mut Self::D4 {
         &mut self.d4
     }
@@ -244,10 +243,6 @@ impl<RS, EN, D4, D5, D6, D7> LcdParallelPins for LcdParallelPinsW4<RS, EN, D4, D
     type RS = RS;
     type RW = LcdParallelWriteOnly;
     type EN = EN;
-    type D0 = NoPin;
-    type D1 = NoPin;
-    type D2 = NoPin;
-    type D3 = NoPin;
     type D4 = D4;
     type D5 = D5;
     type D6 = D6;
@@ -262,18 +257,6 @@ impl<RS, EN, D4, D5, D6, D7> LcdParallelPins for LcdParallelPinsW4<RS, EN, D4, D
     fn en(&mut self) -> &mut Self::EN {
         &mut self.en
     }
-    fn d0(&mut self) -> &mut Self::D0 {
-        panic!()
-    }
-    fn d1(&mut self) -> &mut Self::D1 {
-        panic!()
-    }
-    fn d2(&mut self) -> &mut Self::D2 {
-        panic!()
-    }
-    fn d3(&mut self) -> &mut Self::D3 {
-        panic!()
-    }
     fn d4(&mut self) -> &mut Self::D4 {
         &mut self.d4
     }
@@ -0,0 +1,224 @@
+use core::cell::RefCell;
+
+use embedded_hal::digital::{ErrorType, InputPin, OutputPin};
+
+use crate::bus::timings::{DefaultTimingsParallel4, LcdTimingsParallel};
+
+use super::{
+    sealed, LcdParallelBus, LcdParallelPinsRW4, LcdParallelWriteModeSet, LcdParallelWriteOnly,
+};
+
+/// A pin of a [`LcdSharedParallelBus`] that every display on the bus drives in turn.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdSharedPin<'a, P> {
+    pin: &'a RefCell<P>,
+}
+
+impl<P: ErrorType> ErrorType for LcdSharedPin<'_, P> {
+    type Error = P::Error;
+}
+
+impl<P: OutputPin> OutputPin for LcdSharedPin<'_, P> {
+    #[inline(always)]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.pin.borrow_mut().set_high()
+    }
+
+    #[inline(always)]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.pin.borrow_mut().set_low()
+    }
+}
+
+impl<P: InputPin> InputPin for LcdSharedPin<'_, P> {
+    #[inline(always)]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.pin.borrow_mut().is_high()
+    }
+
+    #[inline(always)]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.pin.borrow_mut().is_low()
+    }
+}
+
+impl sealed::LcdParallelWriteModeSet for LcdSharedPin<'_, LcdParallelWriteOnly> {
+    fn set_write_mode(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl LcdParallelWriteModeSet for LcdSharedPin<'_, LcdParallelWriteOnly> {}
+
+/// A display on a [`LcdSharedParallelBus`], which is a 4-bit [`LcdParallelBus`] with its own
+/// enable pin.
+pub type LcdSharedParallelDisplay<'a, RS, RW, EN, D4, D5, D6, D7, T = DefaultTimingsParallel4> =
+    LcdParallelBus<
+        LcdParallelPinsRW4<
+            LcdSharedPin<'a, RS>,
+            LcdSharedPin<'a, RW>,
+            EN,
+            LcdSharedPin<'a, D4>,
+            LcdSharedPin<'a, D5>,
+            LcdSharedPin<'a, D6>,
+            LcdSharedPin<'a, D7>,
+        >,
+        T,
+        4,
+    >;
+
+/// The RS, RW and D4 to D7 pins of several displays in 4-bit mode that only have separate
+/// enable pins.
+///
+/// A controller ignores the bus while its enable line is low, so every display is a
+/// [`LcdSharedParallelDisplay`] that can be wrapped in its own
+/// [`LcdDriver`](crate::LcdDriver). The displays take turns on the bus, so an access must not
+/// be interrupted by an access to another display, e.g. by polling futures of two displays at
+/// once.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdSharedParallelBus<RS, RW, D4, D5, D6, D7> {
+    rs: RefCell<RS>,
+    rw: RefCell<RW>,
+    d4: RefCell<D4>,
+    d5: RefCell<D5>,
+    d6: RefCell<D6>,
+    d7: RefCell<D7>,
+}
+
+impl<RS, RW, D4, D5, D6, D7> LcdSharedParallelBus<RS, RW, D4, D5, D6, D7>
+where
+    RS: OutputPin,
+    D4: OutputPin,
+    D5: OutputPin,
+    D6: OutputPin,
+    D7: OutputPin,
+    for<'a> LcdSharedPin<'a, RW>: LcdParallelWriteModeSet,
+{
+    /// `rw` is [`LcdParallelWriteOnly`] if it is tied to ground.
+    #[inline]
+    pub fn new(rs: RS, rw: RW, d4: D4, d5: D5, d6: D6, d7: D7) -> Self {
+        Self {
+            rs: RefCell::new(rs),
+            rw: RefCell::new(rw),
+            d4: RefCell::new(d4),
+            d5: RefCell::new(d5),
+            d6: RefCell::new(d6),
+            d7: RefCell::new(d7),
+        }
+    }
+
+    /// The display whose enable pin is `en`.
+    #[inline]
+    pub fn display<EN: OutputPin>(
+        &self,
+        en: EN,
+    ) -> LcdSharedParallelDisplay<'_, RS, RW, EN, D4, D5, D6, D7> {
+        LcdParallelBus::new_4bit(self.pins(en))
+    }
+
+    /// The display whose enable pin is `en`, with different timings.
+    #[inline]
+    pub fn display_with_timings<EN: OutputPin, T, Delay: ?Sized>(
+        &self,
+        en: EN,
+        timings: T,
+    ) -> LcdSharedParallelDisplay<'_, RS, RW, EN, D4, D5, D6, D7, T>
+    where
+        T: LcdTimingsParallel<Delay>,
+    {
+        LcdParallelBus::new_4bit_with_timings(self.pins(en), timings)
+    }
+
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    fn pins<EN>(
+        &self,
+        en: EN,
+    ) -> LcdParallelPinsRW4<
+        LcdSharedPin<'_, RS>,
+        LcdSharedPin<'_, RW>,
+        EN,
+        LcdSharedPin<'_, D4>,
+        LcdSharedPin<'_, D5>,
+        LcdSharedPin<'_, D6>,
+        LcdSharedPin<'_, D7>,
+    > {
+        LcdParallelPinsRW4 {
+            rs: LcdSharedPin { pin: &self.rs },
+            rw: LcdSharedPin { pin: &self.rw },
+            en,
+            d4: LcdSharedPin { pin: &self.d4 },
+            d5: LcdSharedPin { pin: &self.d5 },
+            d6: LcdSharedPin { pin: &self.d6 },
+            d7: LcdSharedPin { pin: &self.d7 },
+        }
+    }
+
+    /// Returns the pins once no display borrows them anymore.
+    #[inline]
+    pub fn destroy(self) -> (RS, RW, D4, D5, D6, D7) {
+        (
+            self.rs.into_inner(),
+            self.rw.into_inner(),
+            self.d4.into_inner(),
+            self.d5.into_inner(),
+            self.d6.into_inner(),
+            self.d7.into_inner(),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::{
+        bus::{blocking::LcdWrite, LcdParallelWriteOnly, LcdRegisterSelect},
+        mock::{MockPin, NoDelay, PinLog},
+    };
+
+    use super::LcdSharedParallelBus;
+
+    /// The entries of the enable pins if `enable` is set, or of the shared pins otherwise.
+    fn filter(log: &[(&'static str, bool)], enable: bool) -> Vec<(&'static str, bool)> {
+        log.iter()
+            .copied()
+            .filter(|(pin, _)| pin.starts_with('E') == enable)
+            .collect()
+    }
+
+    #[test]
+    fn test_displays_only_pulse_their_enable_pin() {
+        let log = PinLog::default();
+        let bus = LcdSharedParallelBus::new(
+            MockPin::new("RS", &log),
+            LcdParallelWriteOnly,
+            MockPin::new("D4", &log),
+            MockPin::new("D5", &log),
+            MockPin::new("D6", &log),
+            MockPin::new("D7", &log),
+        );
+        let mut first = bus.display(MockPin::new("E1", &log));
+        let mut second = bus.display(MockPin::new("E2", &log));
+
+        first
+            .write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+        let split = log.borrow().len();
+        second
+            .write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        let log = log.borrow();
+        let pulses = |en| [(en, true), (en, false), (en, true), (en, false)];
+        assert_eq!(pulses("E1")[..], filter(&log[..split], true));
+        assert_eq!(pulses("E2")[..], filter(&log[split..], true));
+
+        // Both displays put the same levels on the shared lines.
+        assert_eq!(filter(&log[..split], false), filter(&log[split..], false));
+    }
+}
@@ -0,0 +1,267 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{
+        asynch::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        parallel::asynch::{
+            enable_off_async, enable_on_read_async, enable_paced_async, enable_pulse_async,
+            wake_up_async,
+        },
+        AsyncLcdTimingsParallel, LcdInstruction, LcdPace, LcdParallelError, LcdParallelReadModeSet,
+        LcdParallelSignal, LcdParallelWriteModeSet, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{LcdParallelPort, LcdParallelPortBus, LcdParallelPortRead};
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    D: LcdParallelPort,
+{
+    /// Writes a byte in one or two port writes, waiting as long as `pace` asks for.
+    #[inline(always)]
+    async fn write_byte_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        pace: LcdPace,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), LcdParallelError>
+    where
+        T: AsyncLcdTimingsParallel<Delay>,
+    {
+        self.rs
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        if Self::is_8bit() {
+            self.port
+                .write(data)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced_async(&mut self.en, &self.timings, pace, delay).await
+        } else {
+            self.port
+                .write(data >> 4)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced_async(&mut self.en, &self.timings, pace, delay).await?;
+            self.port
+                .write(data & 0x0f)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced_async(&mut self.en, &self.timings, pace, delay).await
+        }
+    }
+}
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+{
+    #[inline(always)]
+    async fn read_port_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: AsyncLcdTimingsParallel<Delay>,
+    {
+        enable_on_read_async(&mut self.en, &self.timings, polling, delay).await?;
+        let data = self
+            .port
+            .read()
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+        enable_off_async(
+            &mut self.en,
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )
+        .await?;
+        Ok(data)
+    }
+
+    /// Reads a byte in one or two port reads.
+    #[inline(always)]
+    async fn read_byte_async<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: AsyncLcdTimingsParallel<Delay>,
+    {
+        self.rw
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.rs
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.port
+            .release()
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+
+        let data = if Self::is_8bit() {
+            self.read_port_async(rs, polling, delay).await?
+        } else {
+            let upper = self.read_port_async(rs, polling, delay).await? & 0x0f;
+            let lower = self.read_port_async(rs, polling, delay).await? & 0x0f;
+            (upper << 4) | lower
+        };
+
+        self.rw
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+
+        Ok(data)
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdWrite<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    D: LcdParallelPort,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    type Error = LcdParallelError;
+
+    #[inline(always)]
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        self.write_byte_async(rs, pace, data, delay).await
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdRead<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let data = self
+            .read_byte_async(LcdRegisterSelect::Control, false, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data))
+    }
+
+    async fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_byte_async(LcdRegisterSelect::Memory, false, delay)
+            .await
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdBusyFlag<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_byte_async(rs, LcdPace::Cycle, data, delay).await
+    }
+
+    async fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self
+            .read_byte_async(LcdRegisterSelect::Control, true, delay)
+            .await?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    async fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay).await;
+        self.timings.enable_pulse_off(instruction, delay).await;
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdInit<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet,
+    EN: OutputPin,
+    D: LcdParallelPort,
+    Delay: DelayNs + ?Sized,
+    T: AsyncLcdTimingsParallel<Delay>,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.rs
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.rw
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.en
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        self.timings.power_on_delay(delay).await;
+
+        self.port
+            .write(if Self::is_8bit() { 0x30 } else { 0x3 })
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+        wake_up_async(&mut self.en, &self.timings, delay).await?;
+
+        let function = if Self::is_8bit() {
+            function.union(LcdFunctionMode::DATA_LENGTH)
+        } else {
+            self.port
+                .write(crate::FUNCTION_SET >> 4)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?; // set 4-bit bus
+            enable_pulse_async(&mut self.en, &self.timings, LcdInstruction::Command, delay).await?;
+            function.difference(LcdFunctionMode::DATA_LENGTH)
+        };
+
+        self.write_command(
+            crate::FUNCTION_SET | function.intersection(LcdFunctionMode::all()).bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )
+        .await?;
+        self.write_command(crate::CLEAR_DISPLAY, delay).await?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+
+use crate::{
+    bus::{
+        blocking::{LcdBusyFlag, LcdInit, LcdRead, LcdWrite},
+        parallel::blocking::{enable_off, enable_on_read, enable_paced, enable_pulse, wake_up},
+        LcdInstruction, LcdPace, LcdParallelError, LcdParallelReadModeSet, LcdParallelSignal,
+        LcdParallelWriteModeSet, LcdRegisterSelect, LcdTimingsParallel,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode, LcdStatus,
+};
+
+use super::{LcdParallelPort, LcdParallelPortBus, LcdParallelPortRead};
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    D: LcdParallelPort,
+{
+    /// Writes a byte in one or two port writes, waiting as long as `pace` asks for.
+    #[inline(always)]
+    fn write_byte<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        pace: LcdPace,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), LcdParallelError>
+    where
+        T: LcdTimingsParallel<Delay>,
+    {
+        self.rs
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        if Self::is_8bit() {
+            self.port
+                .write(data)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced(&mut self.en, &self.timings, pace, delay)
+        } else {
+            self.port
+                .write(data >> 4)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced(&mut self.en, &self.timings, pace, delay)?;
+            self.port
+                .write(data & 0x0f)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+            enable_paced(&mut self.en, &self.timings, pace, delay)
+        }
+    }
+}
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+{
+    #[inline(always)]
+    fn read_port<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: LcdTimingsParallel<Delay>,
+    {
+        enable_on_read(&mut self.en, &self.timings, polling, delay)?;
+        let data = self
+            .port
+            .read()
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+        enable_off(
+            &mut self.en,
+            &self.timings,
+            LcdInstruction::read_pace(rs, polling),
+            delay,
+        )?;
+        Ok(data)
+    }
+
+    /// Reads a byte in one or two port reads.
+    #[inline(always)]
+    fn read_byte<Delay: ?Sized>(
+        &mut self,
+        rs: LcdRegisterSelect,
+        polling: bool,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdParallelError>
+    where
+        T: LcdTimingsParallel<Delay>,
+    {
+        self.rw
+            .set_read_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.rs
+            .set_state(rs.into())
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.port
+            .release()
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+
+        let data = if Self::is_8bit() {
+            self.read_port(rs, polling, delay)?
+        } else {
+            let upper = self.read_port(rs, polling, delay)? & 0x0f;
+            let lower = self.read_port(rs, polling, delay)? & 0x0f;
+            (upper << 4) | lower
+        };
+
+        self.rw
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+
+        Ok(data)
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdWrite<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    EN: OutputPin,
+    D: LcdParallelPort,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    type Error = LcdParallelError;
+
+    #[inline(always)]
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        let pace = LcdPace::Execute(LcdInstruction::decode(rs, data));
+        self.write_byte(rs, pace, data, delay)
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdRead<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    fn read_status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error> {
+        let data = self.read_byte(LcdRegisterSelect::Control, false, delay)?;
+        Ok(LcdStatus::from_bits_retain(data))
+    }
+
+    fn read_data(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        self.read_byte(LcdRegisterSelect::Memory, false, delay)
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdBusyFlag<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet + LcdParallelReadModeSet,
+    EN: OutputPin,
+    D: LcdParallelPortRead,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    fn write_no_wait(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.write_byte(rs, LcdPace::Cycle, data, delay)
+    }
+
+    fn is_busy(&mut self, delay: &mut Delay) -> Result<bool, Self::Error> {
+        let data = self.read_byte(LcdRegisterSelect::Control, true, delay)?;
+        Ok(LcdStatus::from_bits_retain(data).busy())
+    }
+
+    fn wait_fixed(&mut self, instruction: LcdInstruction, delay: &mut Delay) {
+        self.timings.enable_pulse_on(instruction, delay);
+        self.timings.enable_pulse_off(instruction, delay);
+    }
+}
+
+impl<RS, RW, EN, D, T, Delay, const WIDTH: u8> LcdInit<Delay>
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    RS: OutputPin,
+    RW: LcdParallelWriteModeSet,
+    EN: OutputPin,
+    D: LcdParallelPort,
+    Delay: DelayNs + ?Sized,
+    T: LcdTimingsParallel<Delay>,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.rs
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::RegisterSelect))?;
+        self.rw
+            .set_write_mode()
+            .map_err(LcdParallelError::on(LcdParallelSignal::ReadWrite))?;
+        self.en
+            .set_low()
+            .map_err(LcdParallelError::on(LcdParallelSignal::Enable))?;
+        self.timings.power_on_delay(delay);
+
+        self.port
+            .write(if Self::is_8bit() { 0x30 } else { 0x3 })
+            .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?;
+        wake_up(&mut self.en, &self.timings, delay)?;
+
+        let function = if Self::is_8bit() {
+            function.union(LcdFunctionMode::DATA_LENGTH)
+        } else {
+            self.port
+                .write(crate::FUNCTION_SET >> 4)
+                .map_err(LcdParallelError::on(LcdParallelSignal::DataPort))?; // set 4-bit bus
+            enable_pulse(&mut self.en, &self.timings, LcdInstruction::Command, delay)?;
+            function.difference(LcdFunctionMode::DATA_LENGTH)
+        };
+
+        self.write_command(
+            crate::FUNCTION_SET | function.intersection(LcdFunctionMode::all()).bits(),
+            delay,
+        )?;
+        self.write_command(
+            crate::DISPLAY_CONTROL | display.intersection(LcdDisplayMode::all()).bits(),
+            delay,
+        )?;
+        self.write_command(crate::CLEAR_DISPLAY, delay)?;
+        self.write_command(
+            crate::ENTRY_MODE_SET | entry.intersection(LcdEntryMode::all()).bits(),
+            delay,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{collections::VecDeque, vec, vec::Vec};
+
+    use crate::{
+        bus::{
+            blocking::{LcdRead, LcdWrite},
+            LcdParallelPort, LcdParallelPortBus, LcdParallelPortRead, LcdRegisterSelect,
+        },
+        mock::{MockPin, MockPinError, NoDelay, PinLog},
+    };
+
+    /// A port that records its writes and answers reads from a queue.
+    #[derive(Default)]
+    struct MockPort {
+        writes: Vec<u8>,
+        reads: VecDeque<u8>,
+        releases: usize,
+    }
+
+    impl LcdParallelPort for MockPort {
+        type Error = MockPinError;
+
+        fn write(&mut self, data: u8) -> Result<(), Self::Error> {
+            self.writes.push(data);
+            Ok(())
+        }
+    }
+
+    impl LcdParallelPortRead for MockPort {
+        fn release(&mut self) -> Result<(), Self::Error> {
+            self.releases += 1;
+            Ok(())
+        }
+
+        fn read(&mut self) -> Result<u8, Self::Error> {
+            Ok(self.reads.pop_front().unwrap_or(0))
+        }
+    }
+
+    #[test]
+    fn test_write_4bit() {
+        let log = PinLog::default();
+        let mut bus = LcdParallelPortBus::new_4bit(
+            MockPin::new("RS", &log),
+            MockPin::new("RW", &log),
+            MockPin::new("E", &log),
+            MockPort::default(),
+        );
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                ("RS", true),
+                ("E", true),
+                ("E", false),
+                ("E", true),
+                ("E", false)
+            ],
+            *log.borrow()
+        );
+        let (_, _, _, port) = bus.destroy();
+        assert_eq!(vec![0x4, 0x1], port.writes);
+    }
+
+    #[test]
+    fn test_read_4bit() {
+        let log = PinLog::default();
+        let port = MockPort {
+            reads: [0xfa, 0xf5].into(),
+            ..Default::default()
+        };
+        let mut bus = LcdParallelPortBus::new_4bit(
+            MockPin::new("RS", &log),
+            MockPin::new("RW", &log),
+            MockPin::new("E", &log),
+            port,
+        );
+        assert_eq!(0xa5, bus.read_data(&mut NoDelay).unwrap());
+
+        assert_eq!(
+            vec![
+                ("RW", true),
+                ("RS", true),
+                ("E", true),
+                ("E", false),
+                ("E", true),
+                ("E", false),
+                ("RW", false),
+            ],
+            *log.borrow()
+        );
+        let (_, _, _, port) = bus.destroy();
+        assert_eq!(1, port.releases);
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use crate::bus::{timings, LcdController, LcdEnableSelect};
+
+/// Data lines that sit on a single GPIO port and are written with one register store.
+///
+/// On a 4-bit bus only the lower nibble is used, with bit 0 being D4.
+pub trait LcdParallelPort {
+    type Error: embedded_hal::digital::Error;
+
+    /// Drives the data lines with `data` at once, turning them into outputs if they were
+    /// released.
+    fn write(&mut self, data: u8) -> Result<(), Self::Error>;
+}
+
+/// Data lines on a single GPIO port that can also be sampled at once.
+pub trait LcdParallelPortRead: LcdParallelPort {
+    /// Stops driving the data lines, so that the LCD can drive them until the next write.
+    fn release(&mut self) -> Result<(), Self::Error>;
+
+    /// Samples the data lines at once.
+    fn read(&mut self) -> Result<u8, Self::Error>;
+}
+
+/// A parallel bus with the data lines on a [`LcdParallelPort`] and separate control pins.
+///
+/// `RW` is either an output pin or [`LcdParallelWriteOnly`](crate::bus::LcdParallelWriteOnly)
+/// if it is tied to ground.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdParallelPortBus<RS, RW, EN, D, T, const WIDTH: u8> {
+    rs: RS,
+    rw: RW,
+    en: EN,
+    port: D,
+    timings: T,
+}
+
+impl<RS, RW, EN, D> LcdParallelPortBus<RS, RW, EN, D, timings::DefaultTimingsParallel8, 8> {
+    #[inline]
+    pub fn new_8bit(rs: RS, rw: RW, en: EN, port: D) -> Self {
+        Self::new_8bit_with_timings(rs, rw, en, port, timings::DefaultTimingsParallel8)
+    }
+}
+
+impl<RS, RW, EN, D, T> LcdParallelPortBus<RS, RW, EN, D, T, 8> {
+    #[inline]
+    pub fn new_8bit_with_timings(rs: RS, rw: RW, en: EN, port: D, timings: T) -> Self {
+        Self {
+            rs,
+            rw,
+            en,
+            port,
+            timings,
+        }
+    }
+}
+
+impl<RS, RW, EN, D> LcdParallelPortBus<RS, RW, EN, D, timings::DefaultTimingsParallel4, 4> {
+    #[inline]
+    pub fn new_4bit(rs: RS, rw: RW, en: EN, port: D) -> Self {
+        Self::new_4bit_with_timings(rs, rw, en, port, timings::DefaultTimingsParallel4)
+    }
+}
+
+impl<RS, RW, EN, D, T> LcdParallelPortBus<RS, RW, EN, D, T, 4> {
+    #[inline]
+    pub fn new_4bit_with_timings(rs: RS, rw: RW, en: EN, port: D, timings: T) -> Self {
+        Self {
+            rs,
+            rw,
+            en,
+            port,
+            timings,
+        }
+    }
+}
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdEnableSelect
+    for LcdParallelPortBus<RS, RW, EN, D, T, WIDTH>
+where
+    EN: LcdEnableSelect,
+{
+    #[inline]
+    fn select(&mut self, controller: LcdController) {
+        self.en.select(controller);
+    }
+}
+
+impl<RS, RW, EN, D, T, const WIDTH: u8> LcdParallelPortBus<RS, RW, EN, D, T, WIDTH> {
+    /// Whether the data lines are 8 bits wide, failing the build for any width but 4 and 8.
+    #[inline(always)]
+    const fn is_8bit() -> bool {
+        const {
+            assert!(
+                WIDTH == 4 || WIDTH == 8,
+                "LcdParallelPortBus is 4 or 8 bits wide"
+            )
+        };
+        WIDTH == 8
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        &self.timings
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.timings = timings;
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdParallelPortBus<RS, RW, EN, D, T2, WIDTH> {
+        LcdParallelPortBus {
+            rs: self.rs,
+            rw: self.rw,
+            en: self.en,
+            port: self.port,
+            timings,
+        }
+    }
+
+    pub fn destroy(self) -> (RS, RW, EN, D) {
+        (self.rs, self.rw, self.en, self.port)
+    }
+}
@@ -0,0 +1,132 @@
+use embedded_hal::digital::{OutputPin, PinState};
+use embedded_hal_async::{delay::DelayNs, spi::SpiDevice};
+
+use crate::{
+    bus::{
+        asynch::{LcdInit, LcdNibbleTransport, LcdShiftRegisterWiring, LcdWrite},
+        AsyncLcdTimingsNibble, LcdNibbleLevels, LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+};
+
+use super::{LcdShiftRegister2Wire, LcdShiftRegisterBus, LcdShiftRegisterSpi, ShiftRegister};
+
+impl<S> LcdShiftRegisterWiring for LcdShiftRegisterSpi<S>
+where
+    S: SpiDevice,
+{
+    type Error = S::Error;
+
+    async fn shift_out(&mut self, outputs: u8, _enable: u8) -> Result<(), Self::Error> {
+        self.spi.write(&[outputs]).await
+    }
+
+    async fn set_enable(&mut self, outputs: u8, enable: u8, high: bool) -> Result<(), Self::Error> {
+        if high {
+            self.spi.write(&[outputs | enable]).await
+        } else {
+            self.spi.write(&[outputs]).await
+        }
+    }
+}
+
+impl<D, C, E> LcdShiftRegisterWiring for LcdShiftRegister2Wire<D, C>
+where
+    D: OutputPin<Error = E>,
+    C: OutputPin<Error = E>,
+{
+    type Error = E;
+
+    async fn shift_out(&mut self, outputs: u8, enable: u8) -> Result<(), Self::Error> {
+        // Closes the gate first, so that the enable line stays low while shifting.
+        self.shift_byte(0)?;
+        self.shift_byte(outputs | enable)
+    }
+
+    async fn set_enable(
+        &mut self,
+        _outputs: u8,
+        _enable: u8,
+        high: bool,
+    ) -> Result<(), Self::Error> {
+        self.data.set_state(PinState::from(high))
+    }
+}
+
+impl<R> LcdNibbleTransport for ShiftRegister<R>
+where
+    R: LcdShiftRegisterWiring,
+{
+    type Error = R::Error;
+
+    async fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let enable = self.mapping.enable_mask();
+        for levels in levels {
+            let outputs = self.outputs(*levels);
+            // Only the enable line changes during a pulse, which doesn't need another shift.
+            if self.shifted != Some(outputs) {
+                self.shifted = None;
+                self.wiring.shift_out(outputs, enable).await?;
+                self.shifted = Some(outputs);
+                self.enabled = false;
+            }
+            if levels.enable != self.enabled {
+                self.shifted = None;
+                self.wiring
+                    .set_enable(outputs, enable, levels.enable)
+                    .await?;
+                self.shifted = Some(outputs);
+                self.enabled = levels.enable;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R, T> LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+{
+    pub async fn set_backlight_async(&mut self, on: bool) -> Result<(), R::Error> {
+        let register = self.bus.transport_mut();
+        register.backlight = on;
+        register
+            .write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+            .await
+    }
+}
+
+impl<R, T, Delay> LcdWrite<Delay> for LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = R::Error;
+
+    async fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write(rs, data, delay).await
+    }
+}
+
+impl<R, T, Delay> LcdInit<Delay> for LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+    T: AsyncLcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    async fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.init(function, display, entry, delay).await
+    }
+}
@@ -0,0 +1,190 @@
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{OutputPin, PinState},
+    spi::SpiDevice,
+};
+
+use crate::{
+    bus::{
+        blocking::{LcdInit, LcdNibbleTransport, LcdShiftRegisterWiring, LcdWrite},
+        LcdNibbleLevels, LcdRegisterSelect, LcdTimingsNibble,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+};
+
+use super::{LcdShiftRegister2Wire, LcdShiftRegisterBus, LcdShiftRegisterSpi, ShiftRegister};
+
+impl<S> LcdShiftRegisterWiring for LcdShiftRegisterSpi<S>
+where
+    S: SpiDevice,
+{
+    type Error = S::Error;
+
+    fn shift_out(&mut self, outputs: u8, _enable: u8) -> Result<(), Self::Error> {
+        self.spi.write(&[outputs])
+    }
+
+    fn set_enable(&mut self, outputs: u8, enable: u8, high: bool) -> Result<(), Self::Error> {
+        if high {
+            self.spi.write(&[outputs | enable])
+        } else {
+            self.spi.write(&[outputs])
+        }
+    }
+}
+
+impl<D, C, E> LcdShiftRegisterWiring for LcdShiftRegister2Wire<D, C>
+where
+    D: OutputPin<Error = E>,
+    C: OutputPin<Error = E>,
+{
+    type Error = E;
+
+    fn shift_out(&mut self, outputs: u8, enable: u8) -> Result<(), Self::Error> {
+        // Closes the gate first, so that the enable line stays low while shifting.
+        self.shift_byte(0)?;
+        self.shift_byte(outputs | enable)
+    }
+
+    fn set_enable(&mut self, _outputs: u8, _enable: u8, high: bool) -> Result<(), Self::Error> {
+        self.data.set_state(PinState::from(high))
+    }
+}
+
+impl<R> LcdNibbleTransport for ShiftRegister<R>
+where
+    R: LcdShiftRegisterWiring,
+{
+    type Error = R::Error;
+
+    fn write(&mut self, levels: &[LcdNibbleLevels]) -> Result<(), Self::Error> {
+        let enable = self.mapping.enable_mask();
+        for levels in levels {
+            let outputs = self.outputs(*levels);
+            // Only the enable line changes during a pulse, which doesn't need another shift.
+            if self.shifted != Some(outputs) {
+                self.shifted = None;
+                self.wiring.shift_out(outputs, enable)?;
+                self.shifted = Some(outputs);
+                self.enabled = false;
+            }
+            if levels.enable != self.enabled {
+                self.shifted = None;
+                self.wiring.set_enable(outputs, enable, levels.enable)?;
+                self.shifted = Some(outputs);
+                self.enabled = levels.enable;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R, T> LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+{
+    pub fn set_backlight(&mut self, on: bool) -> Result<(), R::Error> {
+        let register = self.bus.transport_mut();
+        register.backlight = on;
+        register.write(&[LcdNibbleLevels::write(LcdRegisterSelect::Control, 0)])
+    }
+}
+
+impl<R, T, Delay> LcdWrite<Delay> for LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+    T: LcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = R::Error;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.write(rs, data, delay)
+    }
+}
+
+impl<R, T, Delay> LcdInit<Delay> for LcdShiftRegisterBus<R, T>
+where
+    R: LcdShiftRegisterWiring,
+    T: LcdTimingsNibble<Delay>,
+    Delay: DelayNs + ?Sized,
+{
+    fn init(
+        &mut self,
+        function: LcdFunctionMode,
+        display: LcdDisplayMode,
+        entry: LcdEntryMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus.init(function, display, entry, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use crate::{
+        bus::{
+            blocking::LcdWrite, LcdRegisterSelect, LcdShiftRegisterBus, LcdShiftRegisterMapping,
+        },
+        mock::{MockPin, MockSpi, NoDelay, PinLog},
+    };
+
+    #[test]
+    fn test_spi_outputs() {
+        let mut bus = LcdShiftRegisterBus::new_spi(
+            MockSpi::default(),
+            LcdShiftRegisterMapping::ADAFRUIT_BACKPACK,
+        );
+        bus.write(LcdRegisterSelect::Memory, 0x41, &mut NoDelay)
+            .unwrap();
+
+        // Backlight on Q7, D4 to D7 on Q6 to Q3, E on Q2 and RS on Q1.
+        assert_eq!(
+            vec![
+                vec![0x92],
+                vec![0x96],
+                vec![0x92],
+                vec![0xc2],
+                vec![0xc6],
+                vec![0xc2],
+            ],
+            bus.destroy().destroy().writes
+        );
+    }
+
+    #[test]
+    fn test_2wire_shifts_msb_first() {
+        let log = PinLog::default();
+        let mut bus = LcdShiftRegisterBus::new_2wire(
+            MockPin::new("data", &log),
+            MockPin::new("clock", &log),
+            LcdShiftRegisterMapping::TWO_WIRE,
+        );
+        bus.set_backlight(true).unwrap();
+
+        // The level of the data pin at each rising clock edge.
+        let mut data = false;
+        let mut bits = Vec::new();
+        for &(pin, high) in log.borrow().iter() {
+            match (pin, high) {
+                ("data", _) => data = high,
+                ("clock", true) => bits.push(data as u8),
+                _ => {}
+            }
+        }
+
+        // The gate is closed with a zero byte first, then QH (E) gets bit 7 and QB the
+        // backlight.
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0], bits);
+        assert_eq!(Some(&("data", false)), log.borrow().last());
+    }
+}
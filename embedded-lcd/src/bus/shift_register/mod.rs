@@ -0,0 +1,239 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use embedded_hal::digital::{OutputPin, PinState};
+
+use crate::bus::timings::DefaultTimingsShiftRegister;
+
+use super::{LcdNibbleBus, LcdNibbleLevels, LcdRegisterSelect, LcdTimingsNibble};
+
+/// The outputs of the shift register the LCD signals are connected to, as bit positions of the
+/// shifted byte. Bit 7 is shifted out first and ends up on the last output (QH).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdShiftRegisterMapping {
+    pub register_select: u8,
+    pub enable: u8,
+    pub backlight: u8,
+    /// The outputs connected to D4 to D7.
+    pub data: [u8; 4],
+}
+
+impl LcdShiftRegisterMapping {
+    /// The 74HC595 of the Adafruit I2C/SPI character LCD backpack.
+    pub const ADAFRUIT_BACKPACK: Self = Self {
+        register_select: 1,
+        enable: 2,
+        backlight: 7,
+        data: [6, 5, 4, 3],
+    };
+
+    /// The usual wiring for [`LcdShiftRegister2Wire`], with the AND gate of the enable line on
+    /// QH and QA left unconnected.
+    pub const TWO_WIRE: Self = Self {
+        register_select: 2,
+        enable: 7,
+        backlight: 1,
+        data: [3, 4, 5, 6],
+    };
+
+    /// The outputs for a nibble with the enable line low.
+    const fn outputs(&self, rs: LcdRegisterSelect, backlight: bool, nibble: u8) -> u8 {
+        let mut outputs = 0;
+        if matches!(rs, LcdRegisterSelect::Memory) {
+            outputs |= 1 << self.register_select;
+        }
+        if backlight {
+            outputs |= 1 << self.backlight;
+        }
+
+        let mut i = 0;
+        while i < 4 {
+            if nibble & (1 << i) != 0 {
+                outputs |= 1 << self.data[i];
+            }
+            i += 1;
+        }
+        outputs
+    }
+
+    #[inline]
+    const fn enable_mask(&self) -> u8 {
+        1 << self.enable
+    }
+}
+
+/// A 74HC595 on SPI, with the latch clock (RCLK) connected to the chip select.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdShiftRegisterSpi<S> {
+    spi: S,
+}
+
+impl<S> LcdShiftRegisterSpi<S> {
+    #[inline]
+    pub fn new(spi: S) -> Self {
+        Self { spi }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> S {
+        self.spi
+    }
+}
+
+/// A shift register without latch such as the 74HC164 on a data and a clock pin.
+///
+/// The enable line of the LCD is driven by a diode AND gate of the data pin and the output for
+/// the enable signal, which has to be QH. The output shifted in last (QA) must stay unconnected,
+/// because the data pin still carries its level when the gate opens.
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdShiftRegister2Wire<D, C> {
+    data: D,
+    clock: C,
+}
+
+impl<D, C> LcdShiftRegister2Wire<D, C> {
+    #[inline]
+    pub fn new(data: D, clock: C) -> Self {
+        Self { data, clock }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> (D, C) {
+        (self.data, self.clock)
+    }
+}
+
+impl<D, C, E> LcdShiftRegister2Wire<D, C>
+where
+    D: OutputPin<Error = E>,
+    C: OutputPin<Error = E>,
+{
+    /// Shifts a byte out starting with bit 7 and leaves the data pin low.
+    fn shift_byte(&mut self, byte: u8) -> Result<(), E> {
+        for bit in (0..8).rev() {
+            self.data
+                .set_state(PinState::from(byte & (1 << bit) != 0))?;
+            self.clock.set_high()?;
+            self.clock.set_low()?;
+        }
+        self.data.set_low()
+    }
+}
+
+/// The shift register of a [`LcdShiftRegisterBus`], which is the
+/// [`LcdNibbleTransport`](crate::bus::blocking::LcdNibbleTransport) of its [`LcdNibbleBus`].
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct ShiftRegister<R> {
+    wiring: R,
+    mapping: LcdShiftRegisterMapping,
+    backlight: bool,
+    /// The outputs in the register, or `None` if they are unknown.
+    shifted: Option<u8>,
+    enabled: bool,
+}
+
+impl<R> ShiftRegister<R> {
+    /// The outputs for `levels` with the enable line low.
+    #[inline]
+    const fn outputs(&self, levels: LcdNibbleLevels) -> u8 {
+        self.mapping.outputs(levels.rs, self.backlight, levels.data)
+    }
+}
+
+/// An LCD in 4-bit mode behind a shift register, a [`LcdNibbleBus`] with a backlight and a
+/// choice of [`LcdShiftRegisterMapping`].
+#[derive(Debug)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LcdShiftRegisterBus<R, T> {
+    bus: LcdNibbleBus<ShiftRegister<R>, T>,
+}
+
+impl<S> LcdShiftRegisterBus<LcdShiftRegisterSpi<S>, DefaultTimingsShiftRegister> {
+    #[inline]
+    pub fn new_spi(spi: S, mapping: LcdShiftRegisterMapping) -> Self {
+        Self::with_wiring(
+            LcdShiftRegisterSpi::new(spi),
+            mapping,
+            DefaultTimingsShiftRegister::default(),
+        )
+    }
+}
+
+impl<D, C> LcdShiftRegisterBus<LcdShiftRegister2Wire<D, C>, DefaultTimingsShiftRegister> {
+    #[inline]
+    pub fn new_2wire(data: D, clock: C, mapping: LcdShiftRegisterMapping) -> Self {
+        Self::with_wiring(
+            LcdShiftRegister2Wire::new(data, clock),
+            mapping,
+            DefaultTimingsShiftRegister::default(),
+        )
+    }
+}
+
+impl<R, T> LcdShiftRegisterBus<R, T> {
+    #[inline]
+    pub fn new_with_timings<Delay: ?Sized>(
+        wiring: R,
+        mapping: LcdShiftRegisterMapping,
+        timings: T,
+    ) -> Self
+    where
+        T: LcdTimingsNibble<Delay>,
+    {
+        Self::with_wiring(wiring, mapping, timings)
+    }
+
+    #[inline]
+    fn with_wiring(wiring: R, mapping: LcdShiftRegisterMapping, timings: T) -> Self {
+        let register = ShiftRegister {
+            wiring,
+            mapping,
+            backlight: true,
+            shifted: None,
+            enabled: false,
+        };
+        Self {
+            bus: LcdNibbleBus::new_with_timings(register, timings),
+        }
+    }
+
+    #[inline]
+    pub fn mapping(&self) -> &LcdShiftRegisterMapping {
+        &self.bus.transport().mapping
+    }
+
+    #[inline]
+    pub fn timings(&self) -> &T {
+        self.bus.timings()
+    }
+
+    /// Replaces the timings of an already constructed bus.
+    #[inline]
+    pub fn set_timings(&mut self, timings: T) {
+        self.bus.set_timings(timings);
+    }
+
+    /// Replaces the timings with timings of a different type.
+    #[inline]
+    pub fn with_timings<T2>(self, timings: T2) -> LcdShiftRegisterBus<R, T2> {
+        LcdShiftRegisterBus {
+            bus: self.bus.with_timings(timings),
+        }
+    }
+
+    #[inline]
+    pub fn destroy(self) -> R {
+        self.bus.destroy().wiring
+    }
+}
@@ -0,0 +1,57 @@
+use embedded_hal_async::i2c::{I2c, Operation, SevenBitAddress};
+
+use super::Tca9548aChannel;
+
+impl<I> I2c<SevenBitAddress> for Tca9548aChannel<'_, I>
+where
+    I: I2c<SevenBitAddress>,
+{
+    // Keeping the bus borrowed until the transaction is done makes a concurrent transaction on
+    // another channel fail with `Busy` instead of switching the segment under this one.
+    #[allow(clippy::await_holding_refcell_ref)]
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut i2c = self.bus()?;
+        if let Some(selection) = self.selection() {
+            self.selected(i2c.write(self.mux.address, &[selection]).await)?;
+        }
+        Ok(i2c.transaction(address, operations).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use embedded_hal_async::i2c::I2c;
+
+    use crate::{
+        bus::{Tca9548a, Tca9548aError},
+        mock::{block_on, MockI2c},
+    };
+
+    #[test]
+    fn test_selection_is_cached() {
+        let mux = Tca9548a::new(MockI2c::new(), Tca9548a::<MockI2c>::DEFAULT_ADDRESS);
+        let mut channel = mux.channel(3).unwrap();
+        block_on(channel.write(0x27, &[0x01])).unwrap();
+        block_on(channel.write(0x27, &[0x02])).unwrap();
+
+        assert_eq!(vec![vec![0x08]], mux.destroy().writes(0x70));
+    }
+
+    #[test]
+    fn test_busy_while_another_channel_transfers() {
+        let mux = Tca9548a::new(MockI2c::new(), Tca9548a::<MockI2c>::DEFAULT_ADDRESS);
+        let _transfer = mux.i2c.borrow_mut();
+        assert_eq!(
+            Err(Tca9548aError::Busy),
+            block_on(mux.channel(0).unwrap().write(0x27, &[0x01]))
+        );
+    }
+}
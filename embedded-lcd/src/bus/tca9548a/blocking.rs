@@ -0,0 +1,59 @@
+use embedded_hal::i2c::{I2c, Operation, SevenBitAddress};
+
+use super::Tca9548aChannel;
+
+impl<I> I2c<SevenBitAddress> for Tca9548aChannel<'_, I>
+where
+    I: I2c<SevenBitAddress>,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut i2c = self.bus()?;
+        if let Some(selection) = self.selection() {
+            self.selected(i2c.write(self.mux.address, &[selection]))?;
+        }
+        Ok(i2c.transaction(address, operations)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec;
+
+    use embedded_hal::i2c::I2c;
+
+    use crate::{
+        bus::{Tca9548a, Tca9548aError},
+        mock::MockI2c,
+    };
+
+    #[test]
+    fn test_selection_is_cached() {
+        let mux = Tca9548a::new(MockI2c::new(), Tca9548a::<MockI2c>::DEFAULT_ADDRESS);
+        let mut first = mux.channel(2).unwrap();
+        first.write(0x27, &[0x01]).unwrap();
+        first.write(0x27, &[0x02]).unwrap();
+        mux.channel(5).unwrap().write(0x27, &[0x03]).unwrap();
+
+        let i2c = mux.destroy();
+        assert_eq!(vec![vec![0x04], vec![0x20]], i2c.writes(0x70));
+        assert_eq!(vec![vec![0x01], vec![0x02], vec![0x03]], i2c.writes(0x27));
+    }
+
+    #[test]
+    fn test_busy_while_another_channel_transfers() {
+        let mux = Tca9548a::new(MockI2c::new(), Tca9548a::<MockI2c>::DEFAULT_ADDRESS);
+        let transfer = mux.i2c.borrow_mut();
+        assert_eq!(
+            Err(Tca9548aError::Busy),
+            mux.channel(0).unwrap().write(0x27, &[0x01])
+        );
+        drop(transfer);
+        assert_eq!(Ok(()), mux.channel(0).unwrap().write(0x27, &[0x01]));
+    }
+}
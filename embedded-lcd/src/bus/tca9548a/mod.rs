@@ -0,0 +1,163 @@
+#[cfg(feature = "async")]
+mod asynch;
+#[cfg(feature = "blocking")]
+mod blocking;
+
+use core::cell::{Cell, RefCell, RefMut};
+
+use embedded_hal::i2c::{ErrorKind, ErrorType};
+
+/// A TCA9548A or PCA9548A I2C multiplexer, which connects the bus to one of eight downstream
+/// segments.
+///
+/// Every [`Tca9548aChannel`] implements [`I2c`](embedded_hal::i2c::I2c) and selects its segment
+/// before forwarding a transaction, so that e.g. several backpacks at the same address can each
+/// get a [`LcdI2c8574Bus`](crate::bus::LcdI2c8574Bus). The selected channel is cached, so that
+/// consecutive transactions on the same channel go straight through.
+///
+/// The channels take turns on the bus, so a transaction must not be interrupted by a transaction
+/// on another channel. A transaction that starts while another one is in progress, e.g. when
+/// futures of two channels are polled at once, fails with [`Tca9548aError::Busy`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tca9548a<I> {
+    i2c: RefCell<I>,
+    address: u8,
+    /// The channel the multiplexer is known to have selected.
+    selected: Cell<Option<u8>>,
+}
+
+impl<I> Tca9548a<I> {
+    /// The address with A0 to A2 tied to ground, the others follow up to `0x77`.
+    pub const DEFAULT_ADDRESS: u8 = 0x70;
+
+    pub const CHANNELS: u8 = 8;
+
+    #[inline]
+    pub fn new(i2c: I, address: u8) -> Self {
+        Self {
+            i2c: RefCell::new(i2c),
+            address,
+            selected: Cell::new(None),
+        }
+    }
+
+    #[inline]
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// The downstream segment `channel`, or `None` if there is no such channel.
+    #[inline]
+    pub fn channel(&self, channel: u8) -> Option<Tca9548aChannel<'_, I>> {
+        (channel < Self::CHANNELS).then_some(Tca9548aChannel { mux: self, channel })
+    }
+
+    /// Forgets the cached selection, e.g. after the multiplexer was reset or written to directly.
+    #[inline]
+    pub fn invalidate(&self) {
+        self.selected.set(None);
+    }
+
+    /// Returns the I2C bus once no channel borrows it anymore.
+    #[inline]
+    pub fn destroy(self) -> I {
+        self.i2c.into_inner()
+    }
+}
+
+/// A downstream segment of a [`Tca9548a`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tca9548aChannel<'a, I> {
+    mux: &'a Tca9548a<I>,
+    channel: u8,
+}
+
+impl<'a, I> Tca9548aChannel<'a, I> {
+    #[inline]
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Borrows the bus for a transaction, unless another channel is in the middle of one.
+    #[inline(always)]
+    fn bus<E>(&self) -> Result<RefMut<'a, I>, Tca9548aError<E>> {
+        self.mux
+            .i2c
+            .try_borrow_mut()
+            .map_err(|_| Tca9548aError::Busy)
+    }
+
+    /// The control register value that connects only this channel, if it is not selected yet.
+    #[inline(always)]
+    fn selection(&self) -> Option<u8> {
+        (self.mux.selected.get() != Some(self.channel)).then_some(1 << self.channel)
+    }
+
+    /// Records the outcome of writing the control register.
+    #[inline(always)]
+    fn selected<E>(&self, result: Result<(), E>) -> Result<(), E> {
+        self.mux
+            .selected
+            .set(result.is_ok().then_some(self.channel));
+        result
+    }
+}
+
+impl<I: ErrorType> ErrorType for Tca9548aChannel<'_, I> {
+    type Error = Tca9548aError<I::Error>;
+}
+
+/// An error of a [`Tca9548aChannel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Tca9548aError<E> {
+    Bus(E),
+    /// A transaction on another channel was in progress.
+    Busy,
+}
+
+impl<E> core::fmt::Display for Tca9548aError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(source) => source.fmt(fmt),
+            Self::Busy => fmt.write_str("another channel is using the bus"),
+        }
+    }
+}
+
+impl<E> core::error::Error for Tca9548aError<E>
+where
+    E: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Bus(source) => Some(source),
+            Self::Busy => None,
+        }
+    }
+}
+
+impl<E> embedded_hal::i2c::Error for Tca9548aError<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    #[inline]
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(source) => source.kind(),
+            Self::Busy => ErrorKind::Other,
+        }
+    }
+}
+
+impl<E> From<E> for Tca9548aError<E> {
+    fn from(value: E) -> Self {
+        Self::Bus(value)
+    }
+}
@@ -1,11 +1,11 @@
 use embedded_hal::delay::DelayNs;
 
-use crate::bus::LcdRegisterSelect;
+use crate::bus::LcdInstruction;
 
 pub trait LcdTimingsI2c<Delay: ?Sized> {
-    fn enable_pulse_on(&self, rs: LcdRegisterSelect, delay: &mut Delay);
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
 
-    fn enable_pulse_off(&self, rs: LcdRegisterSelect, delay: &mut Delay);
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
 
     fn read_delay(&self, delay: &mut Delay);
 
@@ -16,6 +16,22 @@ pub trait LcdTimingsI2c<Delay: ?Sized> {
     fn second_init_delay(&self, delay: &mut Delay);
 }
 
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdTimingsI2c<Delay: ?Sized> {
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn read_delay(&self, delay: &mut Delay);
+
+    async fn power_on_delay(&self, delay: &mut Delay);
+
+    async fn first_init_delay(&self, delay: &mut Delay);
+
+    async fn second_init_delay(&self, delay: &mut Delay);
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -26,18 +42,18 @@ where
     Delay: DelayNs + ?Sized,
 {
     #[inline(always)]
-    fn enable_pulse_on(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_us(750),
-            LcdRegisterSelect::Memory => delay.delay_us(3),
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(750),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(3),
         }
     }
 
     #[inline(always)]
-    fn enable_pulse_off(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_us(800),
-            LcdRegisterSelect::Memory => delay.delay_us(50),
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(800),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50),
         }
     }
 
@@ -61,3 +77,45 @@ where
         delay.delay_us(100);
     }
 }
+
+#[cfg(feature = "async")]
+impl<Delay> AsyncLcdTimingsI2c<Delay> for DefaultTimingsI2c
+where
+    Delay: embedded_hal_async::delay::DelayNs + ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(750).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(3).await,
+        }
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(800).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50).await,
+        }
+    }
+
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_us(800).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ms(50).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(4500).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(100).await;
+    }
+}
@@ -1,5 +1,14 @@
 mod i2c;
 pub use i2c::*;
 
+mod nibble;
+pub use nibble::*;
+
 mod parallel;
 pub use parallel::*;
+
+mod profile;
+pub use profile::*;
+
+mod shift_register;
+pub use shift_register::*;
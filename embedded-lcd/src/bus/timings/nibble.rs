@@ -0,0 +1,130 @@
+#[cfg(feature = "async")]
+use super::AsyncLcdTimingsI2c;
+use super::LcdTimingsI2c;
+
+use crate::bus::LcdInstruction;
+
+/// The timings of a [`LcdNibbleBus`](crate::bus::LcdNibbleBus) that only writes.
+pub trait LcdTimingsNibble<Delay: ?Sized> {
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    fn power_on_delay(&self, delay: &mut Delay);
+
+    fn first_init_delay(&self, delay: &mut Delay);
+
+    fn second_init_delay(&self, delay: &mut Delay);
+}
+
+/// The timings of a [`LcdNibbleBus`](crate::bus::LcdNibbleBus) that can also read.
+pub trait LcdTimingsNibbleRead<Delay: ?Sized>: LcdTimingsNibble<Delay> {
+    fn read_delay(&self, delay: &mut Delay);
+}
+
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdTimingsNibble<Delay: ?Sized> {
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn power_on_delay(&self, delay: &mut Delay);
+
+    async fn first_init_delay(&self, delay: &mut Delay);
+
+    async fn second_init_delay(&self, delay: &mut Delay);
+}
+
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdTimingsNibbleRead<Delay: ?Sized>: AsyncLcdTimingsNibble<Delay> {
+    async fn read_delay(&self, delay: &mut Delay);
+}
+
+// The I2C expanders are nibble buses, so their timings are nibble timings.
+impl<T, Delay> LcdTimingsNibble<Delay> for T
+where
+    T: LcdTimingsI2c<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        LcdTimingsI2c::enable_pulse_on(self, instruction, delay);
+    }
+
+    #[inline(always)]
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        LcdTimingsI2c::enable_pulse_off(self, instruction, delay);
+    }
+
+    #[inline(always)]
+    fn power_on_delay(&self, delay: &mut Delay) {
+        LcdTimingsI2c::power_on_delay(self, delay);
+    }
+
+    #[inline(always)]
+    fn first_init_delay(&self, delay: &mut Delay) {
+        LcdTimingsI2c::first_init_delay(self, delay);
+    }
+
+    #[inline(always)]
+    fn second_init_delay(&self, delay: &mut Delay) {
+        LcdTimingsI2c::second_init_delay(self, delay);
+    }
+}
+
+impl<T, Delay> LcdTimingsNibbleRead<Delay> for T
+where
+    T: LcdTimingsI2c<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    fn read_delay(&self, delay: &mut Delay) {
+        LcdTimingsI2c::read_delay(self, delay);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, Delay> AsyncLcdTimingsNibble<Delay> for T
+where
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::enable_pulse_on(self, instruction, delay).await;
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::enable_pulse_off(self, instruction, delay).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::power_on_delay(self, delay).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::first_init_delay(self, delay).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::second_init_delay(self, delay).await;
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, Delay> AsyncLcdTimingsNibbleRead<Delay> for T
+where
+    T: AsyncLcdTimingsI2c<Delay>,
+    Delay: ?Sized,
+{
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        AsyncLcdTimingsI2c::read_delay(self, delay).await;
+    }
+}
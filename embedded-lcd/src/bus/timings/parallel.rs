@@ -1,11 +1,11 @@
 use embedded_hal::delay::DelayNs;
 
-use crate::bus::LcdRegisterSelect;
+use crate::bus::LcdInstruction;
 
 pub trait LcdTimingsParallel<Delay: ?Sized> {
-    fn enable_pulse_on(&self, rs: LcdRegisterSelect, delay: &mut Delay);
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
 
-    fn enable_pulse_off(&self, rs: LcdRegisterSelect, delay: &mut Delay);
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
 
     fn read_delay(&self, delay: &mut Delay);
 
@@ -16,6 +16,22 @@ pub trait LcdTimingsParallel<Delay: ?Sized> {
     fn second_init_delay(&self, delay: &mut Delay);
 }
 
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdTimingsParallel<Delay: ?Sized> {
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay);
+
+    async fn read_delay(&self, delay: &mut Delay);
+
+    async fn power_on_delay(&self, delay: &mut Delay);
+
+    async fn first_init_delay(&self, delay: &mut Delay);
+
+    async fn second_init_delay(&self, delay: &mut Delay);
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -26,18 +42,18 @@ where
     Delay: DelayNs + ?Sized,
 {
     #[inline(always)]
-    fn enable_pulse_on(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_us(1500),
-            LcdRegisterSelect::Memory => delay.delay_ns(500), // PW_EH >= 230ns on HITACHI specification p. 52
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(1500),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_ns(500), // PW_EH >= 230ns on HITACHI specification p. 52
         }
     }
 
     #[inline(always)]
-    fn enable_pulse_off(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_ms(1),
-            LcdRegisterSelect::Memory => delay.delay_us(50),
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_ms(1),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50),
         }
     }
 
@@ -62,6 +78,48 @@ where
     }
 }
 
+#[cfg(feature = "async")]
+impl<Delay> AsyncLcdTimingsParallel<Delay> for DefaultTimingsParallel8
+where
+    Delay: embedded_hal_async::delay::DelayNs + ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(1500).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_ns(500).await, // PW_EH >= 230ns on HITACHI specification p. 52
+        }
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_ms(1).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50).await,
+        }
+    }
+
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_ms(1).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ms(50).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(4500).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(100).await;
+    }
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -72,18 +130,18 @@ where
     Delay: DelayNs + ?Sized,
 {
     #[inline(always)]
-    fn enable_pulse_on(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_us(750),
-            LcdRegisterSelect::Memory => delay.delay_us(3),
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(750),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(3),
         }
     }
 
     #[inline(always)]
-    fn enable_pulse_off(&self, rs: LcdRegisterSelect, delay: &mut Delay) {
-        match rs {
-            LcdRegisterSelect::Control => delay.delay_us(800),
-            LcdRegisterSelect::Memory => delay.delay_us(50),
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(800),
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50),
         }
     }
 
@@ -107,3 +165,45 @@ where
         delay.delay_us(100);
     }
 }
+
+#[cfg(feature = "async")]
+impl<Delay> AsyncLcdTimingsParallel<Delay> for DefaultTimingsParallel4
+where
+    Delay: embedded_hal_async::delay::DelayNs + ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(750).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(3).await,
+        }
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        match instruction {
+            LcdInstruction::ClearDisplay | LcdInstruction::ReturnHome => delay.delay_us(800).await,
+            LcdInstruction::Command | LcdInstruction::Data => delay.delay_us(50).await,
+        }
+    }
+
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_us(800).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ms(50).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(4500).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_us(100).await;
+    }
+}
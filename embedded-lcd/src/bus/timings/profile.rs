@@ -0,0 +1,240 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::bus::LcdInstruction;
+
+#[cfg(feature = "async")]
+use super::{AsyncLcdTimingsI2c, AsyncLcdTimingsParallel};
+use super::{LcdTimingsI2c, LcdTimingsParallel};
+
+/// Timings that are configurable at runtime.
+///
+/// The enable pulse is split into the time the enable line is held high (`*_on_ns`) and the time
+/// waited after it has been released (`*_off_ns`), which has to cover the execution time of the
+/// instruction. The presets use the datasheet values for an oscillator frequency of 270 kHz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimingProfile {
+    pub clear_display_on_ns: u32,
+    pub clear_display_off_ns: u32,
+    pub return_home_on_ns: u32,
+    pub return_home_off_ns: u32,
+    pub command_on_ns: u32,
+    pub command_off_ns: u32,
+    pub data_on_ns: u32,
+    pub data_off_ns: u32,
+    pub read_delay_ns: u32,
+    pub power_on_delay_ns: u32,
+    pub first_init_delay_ns: u32,
+    pub second_init_delay_ns: u32,
+}
+
+impl TimingProfile {
+    /// Hitachi HD44780U at 4.5 V to 5.5 V.
+    pub const HD44780_5V: Self = Self::new(230, 160, 1_520_000, 37_000, 41_000);
+
+    /// Hitachi HD44780U at 2.7 V to 4.5 V.
+    pub const HD44780_3V3: Self = Self::new(450, 360, 1_520_000, 37_000, 41_000);
+
+    /// Sitronix ST7066U.
+    pub const ST7066U: Self = Self::new(460, 360, 1_520_000, 37_000, 43_000);
+
+    /// Samsung KS0066.
+    pub const KS0066: Self = Self::new(480, 320, 1_530_000, 39_000, 43_000);
+
+    /// Sunplus SPLC780D.
+    pub const SPLC780D: Self = Self::new(450, 360, 1_520_000, 37_000, 43_000);
+
+    /// Derives a profile from the enable pulse width, the data delay time of reads, and the
+    /// execution times of the instruction classes.
+    pub const fn new(
+        pulse_width_ns: u32,
+        read_delay_ns: u32,
+        clear_home_ns: u32,
+        command_ns: u32,
+        data_ns: u32,
+    ) -> Self {
+        Self {
+            clear_display_on_ns: pulse_width_ns,
+            clear_display_off_ns: clear_home_ns,
+            return_home_on_ns: pulse_width_ns,
+            return_home_off_ns: clear_home_ns,
+            command_on_ns: pulse_width_ns,
+            command_off_ns: command_ns,
+            data_on_ns: pulse_width_ns,
+            data_off_ns: data_ns,
+            read_delay_ns,
+            power_on_delay_ns: 40_000_000,
+            first_init_delay_ns: 4_100_000,
+            second_init_delay_ns: 100_000,
+        }
+    }
+
+    #[inline]
+    pub const fn enable_pulse_on_ns(&self, instruction: LcdInstruction) -> u32 {
+        match instruction {
+            LcdInstruction::ClearDisplay => self.clear_display_on_ns,
+            LcdInstruction::ReturnHome => self.return_home_on_ns,
+            LcdInstruction::Command => self.command_on_ns,
+            LcdInstruction::Data => self.data_on_ns,
+        }
+    }
+
+    #[inline]
+    pub const fn enable_pulse_off_ns(&self, instruction: LcdInstruction) -> u32 {
+        match instruction {
+            LcdInstruction::ClearDisplay => self.clear_display_off_ns,
+            LcdInstruction::ReturnHome => self.return_home_off_ns,
+            LcdInstruction::Command => self.command_off_ns,
+            LcdInstruction::Data => self.data_off_ns,
+        }
+    }
+}
+
+impl Default for TimingProfile {
+    fn default() -> Self {
+        Self::HD44780_5V
+    }
+}
+
+impl<Delay> LcdTimingsParallel<Delay> for TimingProfile
+where
+    Delay: DelayNs + ?Sized,
+{
+    #[inline(always)]
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_on_ns(instruction));
+    }
+
+    #[inline(always)]
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_off_ns(instruction));
+    }
+
+    #[inline(always)]
+    fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.read_delay_ns);
+    }
+
+    #[inline(always)]
+    fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.power_on_delay_ns);
+    }
+
+    #[inline(always)]
+    fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.first_init_delay_ns);
+    }
+
+    #[inline(always)]
+    fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.second_init_delay_ns);
+    }
+}
+
+impl<Delay> LcdTimingsI2c<Delay> for TimingProfile
+where
+    Delay: DelayNs + ?Sized,
+{
+    #[inline(always)]
+    fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_on_ns(instruction));
+    }
+
+    #[inline(always)]
+    fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_off_ns(instruction));
+    }
+
+    #[inline(always)]
+    fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.read_delay_ns);
+    }
+
+    #[inline(always)]
+    fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.power_on_delay_ns);
+    }
+
+    #[inline(always)]
+    fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.first_init_delay_ns);
+    }
+
+    #[inline(always)]
+    fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.second_init_delay_ns);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay> AsyncLcdTimingsParallel<Delay> for TimingProfile
+where
+    Delay: embedded_hal_async::delay::DelayNs + ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_on_ns(instruction)).await;
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_off_ns(instruction)).await;
+    }
+
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.read_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.power_on_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.first_init_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.second_init_delay_ns).await;
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay> AsyncLcdTimingsI2c<Delay> for TimingProfile
+where
+    Delay: embedded_hal_async::delay::DelayNs + ?Sized,
+{
+    #[inline(always)]
+    async fn enable_pulse_on(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_on_ns(instruction)).await;
+    }
+
+    #[inline(always)]
+    async fn enable_pulse_off(&self, instruction: LcdInstruction, delay: &mut Delay) {
+        delay.delay_ns(self.enable_pulse_off_ns(instruction)).await;
+    }
+
+    #[inline(always)]
+    async fn read_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.read_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn power_on_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.power_on_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn first_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.first_init_delay_ns).await;
+    }
+
+    #[inline(always)]
+    async fn second_init_delay(&self, delay: &mut Delay) {
+        delay.delay_ns(self.second_init_delay_ns).await;
+    }
+}
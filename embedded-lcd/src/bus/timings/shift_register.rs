@@ -0,0 +1,5 @@
+use super::DefaultTimingsI2c;
+
+/// A shift register is clocked like an I2C expander, so it shares its timings. It cannot read, so
+/// the read delay goes unused.
+pub type DefaultTimingsShiftRegister = DefaultTimingsI2c;
@@ -0,0 +1,417 @@
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{
+    bus::{
+        asynch::{LcdInit, LcdRead, LcdWrite},
+        LcdRegisterSelect,
+    },
+    charset::Charset,
+    driver::{LcdEntryMode, LcdFunctionMode},
+    glyph::Glyph,
+    memory_map::{next_ddram_address, DisplayMemoryMap},
+};
+
+use super::{
+    LcdDisplayMode, LcdDriver, LcdDriverOptions, LcdError, LcdFontMode, LcdInitError, LcdStatus,
+    LcdWrapMode,
+};
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdDriverInit<Delay>: Sized
+where
+    Delay: DelayNs + ?Sized,
+{
+    type MemoryMap: DisplayMemoryMap;
+    type Charset;
+    type Bus: LcdInit<Delay>;
+
+    #[allow(clippy::type_complexity)]
+    async fn init(
+        options: LcdDriverOptions<Self::Bus, Self::MemoryMap, Self::Charset>,
+        delay: &mut Delay,
+    ) -> Result<
+        Self,
+        LcdInitError<
+            LcdDriverOptions<Self::Bus, Self::MemoryMap, Self::Charset>,
+            <Self::Bus as LcdWrite<Delay>>::Error,
+        >,
+    >;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdWrite<Delay: ?Sized> {
+    type Error;
+
+    /// Writes a character code as is, bypassing the charset and the wrap mode.
+    async fn write_code(&mut self, code: u8, delay: &mut Delay) -> Result<(), Self::Error>;
+
+    async fn write_char(&mut self, ch: char, delay: &mut Delay) -> Result<(), Self::Error>;
+
+    async fn write_str(&mut self, s: &str, delay: &mut Delay) -> Result<(), Self::Error> {
+        for ch in s.chars() {
+            self.write_char(ch, delay).await?;
+        }
+        Ok(())
+    }
+}
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdRead<Delay: ?Sized> {
+    type Error;
+
+    async fn status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error>;
+
+    /// Reads the character code at the current address, which is advanced afterwards.
+    async fn read_code(&mut self, delay: &mut Delay) -> Result<u8, Self::Error>;
+
+    /// Reads the character code at a position. Like writing, this moves the address to the next
+    /// cell.
+    async fn read_cell(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<u8, Self::Error>;
+
+    /// Reads the first `N` character codes of a line. Cells that are not on the display read as
+    /// spaces.
+    async fn read_line<const N: usize>(
+        &mut self,
+        y: u8,
+        delay: &mut Delay,
+    ) -> Result<[u8; N], Self::Error>;
+
+    /// Reads a custom character back from a CGRAM slot. The DDRAM address is restored afterwards.
+    async fn read_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        delay: &mut Delay,
+    ) -> Result<Glyph<ROWS>, Self::Error>;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdDriver<Delay: ?Sized> {
+    type Error;
+
+    async fn clear(&mut self, delay: &mut Delay) -> Result<(), Self::Error>;
+
+    async fn return_home(&mut self, delay: &mut Delay) -> Result<(), Self::Error>;
+
+    async fn set_display_mode(
+        &mut self,
+        display_mode: LcdDisplayMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+
+    async fn set_xy(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<(), Self::Error>;
+
+    async fn set_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error>;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait AsyncLcdCgram<Delay: ?Sized> {
+    type Error;
+
+    /// Uploads a custom character into a CGRAM slot. The DDRAM address is restored afterwards.
+    ///
+    /// The glyph is displayed by writing the character code [`Glyph::code`] of its slot. Slots
+    /// beyond [`Glyph::SLOTS`] wrap around.
+    async fn define_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        glyph: &Glyph<ROWS>,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+}
+
+impl<B, M, C, Delay> AsyncLcdDriverInit<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdInit<Delay>,
+    M: DisplayMemoryMap,
+    Delay: DelayNs + ?Sized,
+{
+    type MemoryMap = M;
+    type Charset = C;
+    type Bus = B;
+
+    async fn init(
+        mut options: LcdDriverOptions<Self::Bus, Self::MemoryMap, Self::Charset>,
+        delay: &mut Delay,
+    ) -> Result<
+        Self,
+        LcdInitError<
+            LcdDriverOptions<Self::Bus, Self::MemoryMap, Self::Charset>,
+            <Self::Bus as LcdWrite<Delay>>::Error,
+        >,
+    > {
+        let mut function = LcdFunctionMode::empty();
+
+        // Enables the second memory line for 2-line displays.
+        if options.memory_map.has_two_memory_lines() {
+            function |= LcdFunctionMode::DISPLAY_LINES;
+        }
+
+        if options.font == LcdFontMode::Font5x10 {
+            function |= LcdFunctionMode::FONT;
+        }
+
+        let display_mode = LcdDisplayMode::SHOW_DISPLAY | LcdDisplayMode::SHOW_CURSOR;
+
+        let entry = LcdEntryMode::INCREMENT;
+
+        if let Err(source) = options.bus.init(function, display_mode, entry, delay).await {
+            return Err(LcdInitError { options, source });
+        }
+
+        Ok(Self {
+            bus: options.bus,
+            memory_map: options.memory_map,
+            charset: options.charset,
+            display_mode,
+            address: 0,
+            wrap: options.wrap,
+            clipped: false,
+        })
+    }
+}
+
+impl<B, M, C, Delay> AsyncLcdDriver<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdWrite<Delay>,
+    M: DisplayMemoryMap,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdError<B::Error>;
+
+    async fn clear(&mut self, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus
+            .write(LcdRegisterSelect::Control, crate::CLEAR_DISPLAY, delay)
+            .await
+            .map_err(LcdError::Bus)?;
+        self.address = 0;
+        self.clipped = false;
+        Ok(())
+    }
+
+    async fn return_home(&mut self, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus
+            .write(LcdRegisterSelect::Control, crate::RETURN_HOME, delay)
+            .await
+            .map_err(LcdError::Bus)?;
+        self.address = 0;
+        self.clipped = false;
+        Ok(())
+    }
+
+    async fn set_display_mode(
+        &mut self,
+        display_mode: LcdDisplayMode,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::DISPLAY_CONTROL | display_mode.intersection(LcdDisplayMode::all()).bits(),
+                delay,
+            )
+            .await
+            .map_err(LcdError::Bus)
+    }
+
+    async fn set_xy(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        let Some(address) = self.memory_map.address_for_xy(x, y) else {
+            return Err(LcdError::OutOfBounds { x, y });
+        };
+        self.set_address(address, delay).await
+    }
+
+    async fn set_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus
+            .write_ddram_address(address, delay)
+            .await
+            .map_err(LcdError::Bus)?;
+        self.address = address;
+        self.clipped = false;
+        Ok(())
+    }
+}
+
+impl<B, M, C, Delay> AsyncLcdCgram<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdWrite<Delay>,
+    M: DisplayMemoryMap,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdError<B::Error>;
+
+    async fn define_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        glyph: &Glyph<ROWS>,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::SET_CGRAM_ADDRESS | Glyph::<ROWS>::cgram_address(slot),
+                delay,
+            )
+            .await
+            .map_err(LcdError::Bus)?;
+        for &row in glyph.rows() {
+            self.bus
+                .write(LcdRegisterSelect::Memory, row, delay)
+                .await
+                .map_err(LcdError::Bus)?;
+        }
+
+        // Subsequent memory writes must go to the DDRAM again.
+        self.restore_address_async(delay).await
+    }
+}
+
+impl<B, M, C, Delay> AsyncLcdWrite<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdWrite<Delay>,
+    M: DisplayMemoryMap,
+    C: Charset,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdError<B::Error>;
+
+    async fn write_code(&mut self, code: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus
+            .write(LcdRegisterSelect::Memory, code, delay)
+            .await
+            .map_err(LcdError::Bus)?;
+        self.address = next_ddram_address(self.address, self.memory_map.has_two_memory_lines());
+        Ok(())
+    }
+
+    async fn write_char(&mut self, ch: char, delay: &mut Delay) -> Result<(), Self::Error> {
+        if self.clipped {
+            return Ok(());
+        }
+
+        let code = self
+            .charset
+            .code_from_utf8(ch)
+            .ok_or(LcdError::UnmappableChar(ch))?;
+        let xy = self.xy();
+        self.write_code(code, delay).await?;
+
+        let (width, height) = self.memory_map.display_size();
+        match (xy, self.wrap) {
+            (Some((x, y)), LcdWrapMode::NextLine) if x + 1 == width => {
+                if let Some(address) = self.memory_map.address_for_xy(0, (y + 1) % height) {
+                    self.set_address(address, delay).await?;
+                }
+            }
+            (Some((x, _)), LcdWrapMode::Clip) if x + 1 == width => self.clipped = true,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl<B, M, C, Delay> AsyncLcdRead<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdRead<Delay>,
+    M: DisplayMemoryMap,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdError<B::Error>;
+
+    async fn status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error>
+    where
+        B: LcdRead<Delay>,
+    {
+        self.bus.read_status(delay).await.map_err(LcdError::Bus)
+    }
+
+    async fn read_code(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        // The controller only latches the data for a read when the address is set.
+        self.restore_address_async(delay).await?;
+        self.read_code_unchecked_async(delay).await
+    }
+
+    async fn read_cell(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<u8, Self::Error> {
+        let Some(address) = self.memory_map.address_for_xy(x, y) else {
+            return Err(LcdError::OutOfBounds { x, y });
+        };
+        self.set_address(address, delay).await?;
+        self.read_code_unchecked_async(delay).await
+    }
+
+    async fn read_line<const N: usize>(
+        &mut self,
+        y: u8,
+        delay: &mut Delay,
+    ) -> Result<[u8; N], Self::Error> {
+        let mut line = [b' '; N];
+        let mut latched = false;
+        for (x, code) in line.iter_mut().enumerate() {
+            let Some(address) = self.memory_map.address_for_xy(x as u8, y) else {
+                continue;
+            };
+            if !latched || self.address != address {
+                self.set_address(address, delay).await?;
+                latched = true;
+            }
+            *code = self.read_code_unchecked_async(delay).await?;
+        }
+        Ok(line)
+    }
+
+    async fn read_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        delay: &mut Delay,
+    ) -> Result<Glyph<ROWS>, Self::Error> {
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::SET_CGRAM_ADDRESS | Glyph::<ROWS>::cgram_address(slot),
+                delay,
+            )
+            .await
+            .map_err(LcdError::Bus)?;
+        let mut rows = [0; ROWS];
+        for row in rows.iter_mut() {
+            *row = self.bus.read_data(delay).await.map_err(LcdError::Bus)?;
+        }
+
+        self.restore_address_async(delay).await?;
+        Ok(Glyph::new(rows))
+    }
+}
+
+impl<B, M, C> LcdDriver<B, M, C>
+where
+    M: DisplayMemoryMap,
+{
+    /// Reads at the current address without setting it first.
+    async fn read_code_unchecked_async<Delay>(
+        &mut self,
+        delay: &mut Delay,
+    ) -> Result<u8, LcdError<B::Error>>
+    where
+        B: LcdRead<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let code = self.bus.read_data(delay).await.map_err(LcdError::Bus)?;
+        self.address = next_ddram_address(self.address, self.memory_map.has_two_memory_lines());
+        Ok(code)
+    }
+
+    /// Points the controller at the current address again after accessing the CGRAM or
+    /// latching a read, without ending clipping like moving the cursor does.
+    async fn restore_address_async<Delay>(
+        &mut self,
+        delay: &mut Delay,
+    ) -> Result<(), LcdError<B::Error>>
+    where
+        B: LcdWrite<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        self.bus
+            .write_ddram_address(self.address, delay)
+            .await
+            .map_err(LcdError::Bus)
+    }
+}
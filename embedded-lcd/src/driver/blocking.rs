@@ -5,12 +5,16 @@ use crate::{
         blocking::{LcdInit, LcdRead, LcdWrite},
         LcdRegisterSelect,
     },
-    charset::CharsetWithFallback,
+    charset::Charset,
     driver::{LcdEntryMode, LcdFunctionMode},
-    memory_map::DisplayMemoryMap,
+    glyph::Glyph,
+    memory_map::{next_ddram_address, DisplayMemoryMap},
 };
 
-use super::{LcdDisplayMode, LcdDriver, LcdDriverOptions, LcdFontMode, LcdInitError, LcdStatus};
+use super::{
+    LcdDisplayMode, LcdDriver, LcdDriverOptions, LcdError, LcdFontMode, LcdInitError, LcdStatus,
+    LcdWrapMode, LcdWriter,
+};
 
 pub trait BlockingLcdDriverInit<Delay>: Sized
 where
@@ -42,6 +46,9 @@ pub trait BlockingLcdDriverDestroy {
 pub trait BlockingLcdWrite<Delay: ?Sized> {
     type Error;
 
+    /// Writes a character code as is, bypassing the charset and the wrap mode.
+    fn write_code(&mut self, code: u8, delay: &mut Delay) -> Result<(), Self::Error>;
+
     fn write_char(&mut self, ch: char, delay: &mut Delay) -> Result<(), Self::Error>;
 
     fn write_str(&mut self, s: &str, delay: &mut Delay) -> Result<(), Self::Error> {
@@ -50,12 +57,42 @@ pub trait BlockingLcdWrite<Delay: ?Sized> {
         }
         Ok(())
     }
+
+    /// An adapter that implements [`core::fmt::Write`] and `ufmt::uWrite`.
+    fn writer<'a>(&'a mut self, delay: &'a mut Delay) -> LcdWriter<'a, Self, Delay>
+    where
+        Self: Sized,
+    {
+        LcdWriter::new(self, delay)
+    }
 }
 
 pub trait BlockingLcdRead<Delay: ?Sized> {
     type Error;
 
     fn status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error>;
+
+    /// Reads the character code at the current address, which is advanced afterwards.
+    fn read_code(&mut self, delay: &mut Delay) -> Result<u8, Self::Error>;
+
+    /// Reads the character code at a position. Like writing, this moves the address to the next
+    /// cell.
+    fn read_cell(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<u8, Self::Error>;
+
+    /// Reads the first `N` character codes of a line. Cells that are not on the display read as
+    /// spaces.
+    fn read_line<const N: usize>(
+        &mut self,
+        y: u8,
+        delay: &mut Delay,
+    ) -> Result<[u8; N], Self::Error>;
+
+    /// Reads a custom character back from a CGRAM slot. The DDRAM address is restored afterwards.
+    fn read_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        delay: &mut Delay,
+    ) -> Result<Glyph<ROWS>, Self::Error>;
 }
 
 pub trait BlockingLcdDriver<Delay: ?Sized> {
@@ -76,6 +113,21 @@ pub trait BlockingLcdDriver<Delay: ?Sized> {
     fn set_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error>;
 }
 
+pub trait BlockingLcdCgram<Delay: ?Sized> {
+    type Error;
+
+    /// Uploads a custom character into a CGRAM slot. The DDRAM address is restored afterwards.
+    ///
+    /// The glyph is displayed by writing the character code [`Glyph::code`] of its slot. Slots
+    /// beyond [`Glyph::SLOTS`] wrap around.
+    fn define_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        glyph: &Glyph<ROWS>,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error>;
+}
+
 impl<B, M, C, Delay> BlockingLcdDriverInit<Delay> for LcdDriver<B, M, C>
 where
     B: LcdInit<Delay>,
@@ -120,6 +172,9 @@ where
             memory_map: options.memory_map,
             charset: options.charset,
             display_mode,
+            address: 0,
+            wrap: options.wrap,
+            clipped: false,
         })
     }
 }
@@ -130,16 +185,24 @@ where
     M: DisplayMemoryMap,
     Delay: DelayNs + ?Sized,
 {
-    type Error = B::Error;
+    type Error = LcdError<B::Error>;
 
     fn clear(&mut self, delay: &mut Delay) -> Result<(), Self::Error> {
         self.bus
             .write(LcdRegisterSelect::Control, crate::CLEAR_DISPLAY, delay)
+            .map_err(LcdError::Bus)?;
+        self.address = 0;
+        self.clipped = false;
+        Ok(())
     }
 
     fn return_home(&mut self, delay: &mut Delay) -> Result<(), Self::Error> {
         self.bus
             .write(LcdRegisterSelect::Control, crate::RETURN_HOME, delay)
+            .map_err(LcdError::Bus)?;
+        self.address = 0;
+        self.clipped = false;
+        Ok(())
     }
 
     fn set_display_mode(
@@ -147,45 +210,104 @@ where
         display_mode: LcdDisplayMode,
         delay: &mut Delay,
     ) -> Result<(), Self::Error> {
-        self.bus.write(
-            LcdRegisterSelect::Control,
-            crate::DISPLAY_CONTROL | display_mode.intersection(LcdDisplayMode::all()).bits(),
-            delay,
-        )
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::DISPLAY_CONTROL | display_mode.intersection(LcdDisplayMode::all()).bits(),
+                delay,
+            )
+            .map_err(LcdError::Bus)
     }
 
     fn set_xy(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<(), Self::Error> {
         let Some(address) = self.memory_map.address_for_xy(x, y) else {
-            return Ok(()); // TODO: Better error handling
+            return Err(LcdError::OutOfBounds { x, y });
         };
         self.set_address(address, delay)
     }
 
     fn set_address(&mut self, address: u8, delay: &mut Delay) -> Result<(), Self::Error> {
-        self.bus.write(
-            LcdRegisterSelect::Control,
-            crate::SET_DDRAM_ADDRESS | address,
-            delay,
-        )?;
+        self.bus
+            .write_ddram_address(address, delay)
+            .map_err(LcdError::Bus)?;
+        self.address = address;
+        self.clipped = false;
         Ok(())
     }
 }
 
+impl<B, M, C, Delay> BlockingLcdCgram<Delay> for LcdDriver<B, M, C>
+where
+    B: LcdWrite<Delay>,
+    M: DisplayMemoryMap,
+    Delay: DelayNs + ?Sized,
+{
+    type Error = LcdError<B::Error>;
+
+    fn define_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        glyph: &Glyph<ROWS>,
+        delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::SET_CGRAM_ADDRESS | Glyph::<ROWS>::cgram_address(slot),
+                delay,
+            )
+            .map_err(LcdError::Bus)?;
+        for &row in glyph.rows() {
+            self.bus
+                .write(LcdRegisterSelect::Memory, row, delay)
+                .map_err(LcdError::Bus)?;
+        }
+
+        // Subsequent memory writes must go to the DDRAM again.
+        self.restore_address(delay)
+    }
+}
+
 impl<B, M, C, Delay> BlockingLcdWrite<Delay> for LcdDriver<B, M, C>
 where
     B: LcdWrite<Delay>,
     M: DisplayMemoryMap,
-    C: CharsetWithFallback,
+    C: Charset,
     Delay: DelayNs + ?Sized,
 {
-    type Error = B::Error;
+    type Error = LcdError<B::Error>;
+
+    fn write_code(&mut self, code: u8, delay: &mut Delay) -> Result<(), Self::Error> {
+        self.bus
+            .write(LcdRegisterSelect::Memory, code, delay)
+            .map_err(LcdError::Bus)?;
+        self.address = next_ddram_address(self.address, self.memory_map.has_two_memory_lines());
+        Ok(())
+    }
 
     fn write_char(&mut self, ch: char, delay: &mut Delay) -> Result<(), Self::Error> {
-        self.bus.write(
-            LcdRegisterSelect::Memory,
-            self.charset.code_from_utf8_with_fallback(ch),
-            delay,
-        )
+        if self.clipped {
+            return Ok(());
+        }
+
+        let code = self
+            .charset
+            .code_from_utf8(ch)
+            .ok_or(LcdError::UnmappableChar(ch))?;
+        let xy = self.xy();
+        self.write_code(code, delay)?;
+
+        let (width, height) = self.memory_map.display_size();
+        match (xy, self.wrap) {
+            (Some((x, y)), LcdWrapMode::NextLine) if x + 1 == width => {
+                if let Some(address) = self.memory_map.address_for_xy(0, (y + 1) % height) {
+                    self.set_address(address, delay)?;
+                }
+            }
+            (Some((x, _)), LcdWrapMode::Clip) if x + 1 == width => self.clipped = true,
+            _ => {}
+        }
+        Ok(())
     }
 }
 
@@ -193,15 +315,125 @@ impl<B, M, C, Delay> BlockingLcdRead<Delay> for LcdDriver<B, M, C>
 where
     B: LcdRead<Delay>,
     M: DisplayMemoryMap,
-    C: CharsetWithFallback,
     Delay: DelayNs + ?Sized,
 {
-    type Error = B::Error;
+    type Error = LcdError<B::Error>;
 
     fn status(&mut self, delay: &mut Delay) -> Result<LcdStatus, Self::Error>
     where
         B: LcdRead<Delay>,
     {
-        self.bus.read_status(delay)
+        self.bus.read_status(delay).map_err(LcdError::Bus)
+    }
+
+    fn read_code(&mut self, delay: &mut Delay) -> Result<u8, Self::Error> {
+        // The controller only latches the data for a read when the address is set.
+        self.restore_address(delay)?;
+        self.read_code_unchecked(delay)
+    }
+
+    fn read_cell(&mut self, x: u8, y: u8, delay: &mut Delay) -> Result<u8, Self::Error> {
+        let Some(address) = self.memory_map.address_for_xy(x, y) else {
+            return Err(LcdError::OutOfBounds { x, y });
+        };
+        self.set_address(address, delay)?;
+        self.read_code_unchecked(delay)
+    }
+
+    fn read_line<const N: usize>(
+        &mut self,
+        y: u8,
+        delay: &mut Delay,
+    ) -> Result<[u8; N], Self::Error> {
+        let mut line = [b' '; N];
+        let mut latched = false;
+        for (x, code) in line.iter_mut().enumerate() {
+            let Some(address) = self.memory_map.address_for_xy(x as u8, y) else {
+                continue;
+            };
+            if !latched || self.address != address {
+                self.set_address(address, delay)?;
+                latched = true;
+            }
+            *code = self.read_code_unchecked(delay)?;
+        }
+        Ok(line)
+    }
+
+    fn read_glyph<const ROWS: usize>(
+        &mut self,
+        slot: u8,
+        delay: &mut Delay,
+    ) -> Result<Glyph<ROWS>, Self::Error> {
+        self.bus
+            .write(
+                LcdRegisterSelect::Control,
+                crate::SET_CGRAM_ADDRESS | Glyph::<ROWS>::cgram_address(slot),
+                delay,
+            )
+            .map_err(LcdError::Bus)?;
+        let mut rows = [0; ROWS];
+        for row in rows.iter_mut() {
+            *row = self.bus.read_data(delay).map_err(LcdError::Bus)?;
+        }
+
+        self.restore_address(delay)?;
+        Ok(Glyph::new(rows))
+    }
+}
+
+impl<B, M, C> LcdDriver<B, M, C>
+where
+    M: DisplayMemoryMap,
+{
+    /// Reads at the current address without setting it first.
+    fn read_code_unchecked<Delay>(&mut self, delay: &mut Delay) -> Result<u8, LcdError<B::Error>>
+    where
+        B: LcdRead<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        let code = self.bus.read_data(delay).map_err(LcdError::Bus)?;
+        self.address = next_ddram_address(self.address, self.memory_map.has_two_memory_lines());
+        Ok(code)
+    }
+
+    /// Points the controller at the current address again after accessing the CGRAM or
+    /// latching a read, without ending clipping like moving the cursor does.
+    fn restore_address<Delay>(&mut self, delay: &mut Delay) -> Result<(), LcdError<B::Error>>
+    where
+        B: LcdWrite<Delay>,
+        Delay: DelayNs + ?Sized,
+    {
+        self.bus
+            .write_ddram_address(self.address, delay)
+            .map_err(LcdError::Bus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        blocking::{BlockingLcdCgram, BlockingLcdDriver, BlockingLcdDriverInit, BlockingLcdWrite},
+        mock::{CountingBus, NoDelay},
+        Glyph5x8, LcdDriver, LcdDriverOptions, LcdWrapMode, MemoryMap1602,
+    };
+
+    #[test]
+    fn test_define_glyph_keeps_clipping() {
+        let bus = CountingBus::default();
+        let mut lcd = LcdDriver::init(
+            LcdDriverOptions::new(bus.clone(), MemoryMap1602::new()),
+            &mut NoDelay,
+        )
+        .unwrap();
+        lcd.set_wrap_mode(LcdWrapMode::Clip);
+
+        lcd.set_xy(15, 0, &mut NoDelay).unwrap();
+        lcd.write_char('a', &mut NoDelay).unwrap();
+        lcd.define_glyph(0, &Glyph5x8::default(), &mut NoDelay)
+            .unwrap();
+        lcd.write_char('b', &mut NoDelay).unwrap();
+
+        assert_eq!(1 + 8, bus.writes());
     }
 }
@@ -2,9 +2,18 @@ use core::{error::Error, fmt::Debug};
 
 use bitflags::bitflags;
 
+use crate::DisplayMemoryMap;
+
+#[cfg(feature = "async")]
+pub mod asynch;
+#[cfg(feature = "blocking")]
 pub mod blocking;
 mod options;
 pub use options::*;
+#[cfg(feature = "blocking")]
+mod writer;
+#[cfg(feature = "blocking")]
+pub use writer::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
@@ -14,6 +23,11 @@ pub struct LcdDriver<B, M, C> {
     memory_map: M,
     charset: C,
     display_mode: LcdDisplayMode,
+    /// The DDRAM address the controller's address counter is expected to point at.
+    address: u8,
+    wrap: LcdWrapMode,
+    /// Whether text is dropped because it ran past the end of a line in [`LcdWrapMode::Clip`].
+    clipped: bool,
 }
 
 impl<B, M, C> LcdDriver<B, M, C> {
@@ -24,6 +38,27 @@ impl<B, M, C> LcdDriver<B, M, C> {
     pub fn charset(&self) -> &C {
         &self.charset
     }
+
+    /// The DDRAM address the next character is written to.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    pub fn wrap_mode(&self) -> LcdWrapMode {
+        self.wrap
+    }
+
+    pub fn set_wrap_mode(&mut self, wrap: LcdWrapMode) {
+        self.wrap = wrap;
+    }
+}
+
+impl<B, M: DisplayMemoryMap, C> LcdDriver<B, M, C> {
+    /// The position the next character is written to, or `None` if the address is not on the
+    /// display.
+    pub fn xy(&self) -> Option<(u8, u8)> {
+        self.memory_map.xy_for_address(self.address)
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -88,6 +123,83 @@ where
     }
 }
 
+/// An error of the display driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdError<E> {
+    /// The bus failed to communicate with the controller.
+    Bus(E),
+    /// The position is not on the display.
+    OutOfBounds { x: u8, y: u8 },
+    /// The charset has no code for the character.
+    UnmappableChar(char),
+}
+
+impl<E> core::fmt::Display for LcdError<E>
+where
+    E: core::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(source) => source.fmt(fmt),
+            Self::OutOfBounds { x, y } => write!(fmt, "position ({x}, {y}) is not on the display"),
+            Self::UnmappableChar(ch) => write!(fmt, "no character code for {ch:?}"),
+        }
+    }
+}
+
+impl<E> Error for LcdError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Bus(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<E> ufmt::uDebug for LcdError<E>
+where
+    E: ufmt::uDebug,
+{
+    fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        match self {
+            Self::Bus(source) => fmt.debug_tuple("Bus")?.field(source)?.finish(),
+            Self::OutOfBounds { x, y } => fmt
+                .debug_struct("OutOfBounds")?
+                .field("x", x)?
+                .field("y", y)?
+                .finish(),
+            Self::UnmappableChar(ch) => fmt
+                .debug_tuple("UnmappableChar")?
+                .field(&QuotedChar(*ch))?
+                .finish(),
+        }
+    }
+}
+
+/// Shows a character like `Debug` does, as ufmt only implements `uDisplay` for `char`.
+#[cfg(feature = "ufmt")]
+struct QuotedChar(char);
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDebug for QuotedChar {
+    fn fmt<W>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        fmt.write_char('\'')?;
+        fmt.write_char(self.0)?;
+        fmt.write_char('\'')
+    }
+}
+
 bitflags! {
     #[derive(Clone, Copy, PartialEq, Eq)]
     pub struct LcdStatus: u8 {
@@ -8,6 +8,7 @@ pub struct LcdDriverOptions<B, M: DisplayMemoryMap, C> {
     pub memory_map: M,
     pub charset: C,
     pub font: LcdFontMode,
+    pub wrap: LcdWrapMode,
 }
 
 impl<B, M: DisplayMemoryMap> LcdDriverOptions<B, M, EmptyFallback<CharsetUniversal>> {
@@ -18,6 +19,7 @@ impl<B, M: DisplayMemoryMap> LcdDriverOptions<B, M, EmptyFallback<CharsetUnivers
             memory_map,
             charset: CharsetUniversal::EMPTY_FALLBACK,
             font: LcdFontMode::Font5x8,
+            wrap: LcdWrapMode::NextLine,
         }
     }
 }
@@ -29,8 +31,13 @@ impl<B, M: DisplayMemoryMap, C> LcdDriverOptions<B, M, C> {
             memory_map: self.memory_map,
             charset,
             font: self.font,
+            wrap: self.wrap,
         }
     }
+
+    pub fn with_wrap(self, wrap: LcdWrapMode) -> Self {
+        Self { wrap, ..self }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -41,3 +48,17 @@ pub enum LcdFontMode {
     Font5x8,
     Font5x10,
 }
+
+/// What happens when text is written past the end of a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum LcdWrapMode {
+    /// Continue at the start of the line below, and at the top after the last line.
+    #[default]
+    NextLine,
+    /// Drop characters until the cursor is moved.
+    Clip,
+    /// Continue at the next DDRAM address, e.g. on the third line after the first line of a 2004.
+    Ddram,
+}
@@ -0,0 +1,155 @@
+use core::fmt;
+
+use super::blocking::BlockingLcdWrite;
+
+/// Borrows a driver and a delay to make the display a target for `write!` and `uwrite!`.
+///
+/// [`fmt::Error`] cannot carry the cause of a failed write, so the error of the driver is kept
+/// and can be retrieved with [`LcdWriter::take_error`] or [`LcdWriter::finish`].
+pub struct LcdWriter<'a, D, Delay>
+where
+    D: BlockingLcdWrite<Delay>,
+    Delay: ?Sized,
+{
+    driver: &'a mut D,
+    delay: &'a mut Delay,
+    error: Option<D::Error>,
+}
+
+impl<'a, D, Delay> LcdWriter<'a, D, Delay>
+where
+    D: BlockingLcdWrite<Delay>,
+    Delay: ?Sized,
+{
+    pub fn new(driver: &'a mut D, delay: &'a mut Delay) -> Self {
+        Self {
+            driver,
+            delay,
+            error: None,
+        }
+    }
+
+    /// The error of the last failed write, if any.
+    pub fn take_error(&mut self) -> Option<D::Error> {
+        self.error.take()
+    }
+
+    /// Releases the driver and returns the error of the last failed write, if any.
+    pub fn finish(self) -> Result<(), D::Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<D, Delay> fmt::Write for LcdWriter<'_, D, Delay>
+where
+    D: BlockingLcdWrite<Delay>,
+    Delay: ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.driver.write_str(s, self.delay).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+
+    fn write_char(&mut self, ch: char) -> fmt::Result {
+        self.driver.write_char(ch, self.delay).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<D, Delay> ufmt::uWrite for LcdWriter<'_, D, Delay>
+where
+    D: BlockingLcdWrite<Delay>,
+    Delay: ?Sized,
+{
+    type Error = D::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.driver.write_str(s, self.delay)
+    }
+
+    fn write_char(&mut self, ch: char) -> Result<(), Self::Error> {
+        self.driver.write_char(ch, self.delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::{
+        blocking::{BlockingLcdDriverInit, BlockingLcdWrite},
+        mock::{CountingBus, MockPinError, NoDelay},
+        Charset, LcdDriver, LcdDriverOptions, LcdError, MemoryMap1602,
+    };
+
+    fn driver(bus: &CountingBus) -> LcdDriver<CountingBus, MemoryMap1602, impl Charset> {
+        LcdDriver::init(
+            LcdDriverOptions::new(bus.clone(), MemoryMap1602::new()),
+            &mut NoDelay,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write() {
+        let bus = CountingBus::default();
+        let mut lcd = driver(&bus);
+        let mut delay = NoDelay;
+        let mut writer = lcd.writer(&mut delay);
+
+        write!(writer, "t={}", 42).unwrap();
+        assert_eq!(Ok(()), writer.finish());
+        assert_eq!(b"t=42".as_slice(), bus.memory());
+    }
+
+    #[test]
+    fn test_write_keeps_the_bus_error() {
+        let bus = CountingBus::failing_after(2);
+        let mut lcd = driver(&bus);
+        let mut delay = NoDelay;
+        let mut writer = lcd.writer(&mut delay);
+
+        assert!(write!(writer, "t={}", 42).is_err());
+        assert_eq!(Some(LcdError::Bus(MockPinError)), writer.take_error());
+        assert_eq!(None, writer.take_error());
+
+        assert!(write!(writer, "{}", 7).is_err());
+        assert_eq!(Err(LcdError::Bus(MockPinError)), writer.finish());
+        assert_eq!(b"t=".as_slice(), bus.memory());
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_uwrite() {
+        let bus = CountingBus::default();
+        let mut lcd = driver(&bus);
+        let mut delay = NoDelay;
+        let mut writer = lcd.writer(&mut delay);
+
+        assert_eq!(Ok(()), ufmt::uwrite!(writer, "t={}", 42));
+        assert_eq!(b"t=42".as_slice(), bus.memory());
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_uwrite_returns_the_bus_error() {
+        let bus = CountingBus::failing_after(2);
+        let mut lcd = driver(&bus);
+        let mut delay = NoDelay;
+        let mut writer = lcd.writer(&mut delay);
+
+        assert_eq!(
+            Err(LcdError::Bus(MockPinError)),
+            ufmt::uwrite!(writer, "t={}", 42)
+        );
+        assert_eq!(b"t=".as_slice(), bus.memory());
+    }
+}
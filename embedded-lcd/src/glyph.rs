@@ -0,0 +1,63 @@
+pub type Glyph5x8 = Glyph<8>;
+pub type Glyph5x10 = Glyph<11>;
+
+/// A user-defined character that can be uploaded into the character generator RAM (CGRAM).
+///
+/// Every row holds the five pixels of one character line in its lower five bits, bit 4 being the
+/// leftmost pixel. The last row is the line that is also used by the cursor. 5x8 glyphs have 8
+/// rows and 5x10 glyphs have 11 rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Glyph<const ROWS: usize> {
+    rows: [u8; ROWS],
+}
+
+impl<const ROWS: usize> Glyph<ROWS> {
+    /// Number of CGRAM bytes occupied by one glyph.
+    const STRIDE: u8 = if ROWS > 8 { 16 } else { 8 };
+
+    /// Number of glyphs that fit into the CGRAM.
+    pub const SLOTS: u8 = 64 / Self::STRIDE;
+
+    /// Creates a glyph from its pixel rows. Bits outside of the lower five are ignored.
+    pub const fn new(rows: [u8; ROWS]) -> Self {
+        const { assert!(ROWS == 8 || ROWS == 11, "glyphs have either 8 or 11 rows") };
+
+        let mut rows = rows;
+        let mut i = 0;
+        while i < ROWS {
+            rows[i] &= 0x1f;
+            i += 1;
+        }
+        Self { rows }
+    }
+
+    pub const fn rows(&self) -> &[u8; ROWS] {
+        &self.rows
+    }
+
+    /// The CGRAM address of the first row of a slot.
+    #[inline]
+    pub(crate) const fn cgram_address(slot: u8) -> u8 {
+        (slot % Self::SLOTS) * Self::STRIDE
+    }
+
+    /// The character code that displays the glyph stored in a slot.
+    ///
+    /// In 5x10 mode, the lowest bit of the character code is ignored by the controller, which is
+    /// why 5x10 slots map to even codes.
+    pub const fn code(slot: u8) -> u8 {
+        let slot = slot % Self::SLOTS;
+        if ROWS > 8 {
+            slot << 1
+        } else {
+            slot
+        }
+    }
+}
+
+impl<const ROWS: usize> Default for Glyph<ROWS> {
+    fn default() -> Self {
+        Self::new([0; ROWS])
+    }
+}
@@ -1,4 +1,7 @@
 #![no_std]
+// Without a bus flavor, the register maps and helpers shared by the blocking and async buses are
+// unused.
+#![cfg_attr(not(any(feature = "blocking", feature = "async")), allow(dead_code))]
 
 pub mod bus;
 
@@ -11,10 +14,20 @@ pub use charset::*;
 mod memory_map;
 pub use memory_map::*;
 
+mod glyph;
+pub use glyph::*;
+
+mod buffered;
+pub use buffered::*;
+
+#[cfg(test)]
+mod mock;
+
 const CLEAR_DISPLAY: u8 = 0x01;
 const RETURN_HOME: u8 = 0x02;
 const ENTRY_MODE_SET: u8 = 0x04;
 const DISPLAY_CONTROL: u8 = 0x08;
+#[allow(dead_code)]
 const CURSOR_SHIFT: u8 = 0x10;
 const FUNCTION_SET: u8 = 0x20;
 const SET_CGRAM_ADDRESS: u8 = 0x40;
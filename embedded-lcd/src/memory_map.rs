@@ -5,6 +5,8 @@ pub type MemoryMap1604 = StandardMemoryMap<16, 4>;
 pub type MemoryMap2002 = StandardMemoryMap<20, 4>;
 pub type MemoryMap2004 = StandardMemoryMap<20, 4>;
 pub type MemoryMap4002 = StandardMemoryMap<40, 2>;
+pub type MemoryMap2704 = DualControllerMemoryMap<27>;
+pub type MemoryMap4004 = DualControllerMemoryMap<40>;
 
 pub trait DisplayMemoryMap {
     /// The address of a character on the display respecting the scrollable margin.
@@ -18,8 +20,21 @@ pub trait DisplayMemoryMap {
 
     /// Whether the display uses two memory lines.
     fn has_two_memory_lines(&self) -> bool;
+
+    /// The position of a character on the display for an address, the reverse of
+    /// [`DisplayMemoryMap::address_for_xy`].
+    fn xy_for_address(&self, address: u8) -> Option<(u8, u8)> {
+        let (_, height) = self.display_size();
+        (0..height).find_map(|y| {
+            (0..self.line_width(y))
+                .find(|&x| self.address_for_xy(x, y) == Some(address))
+                .map(|x| (x, y))
+        })
+    }
 }
 
+/// The memory map of a display with a single controller and 1 to 4 lines, where `LINE_WIDTH` is
+/// the length of a memory line. Other heights fail to compile.
 pub struct StandardMemoryMap<const WIDTH: u8, const HEIGHT: u8, const LINE_WIDTH: u8 = 40>;
 
 impl<const W: u8, const H: u8, const L: u8> StandardMemoryMap<W, H, L> {
@@ -54,12 +69,17 @@ impl<const W: u8, const H: u8, const L: u8> DisplayMemoryMap for StandardMemoryM
     }
 
     fn line_width(&self, y: u8) -> u8 {
-        let scrollable: u8 = const { scrollable_margin(W, H, L) };
+        let scrollable: u8 = const {
+            assert!(
+                matches!(H, 1..=4),
+                "StandardMemoryMap covers displays with 1 to 4 lines"
+            );
+            scrollable_margin(W, H, L)
+        };
         match H {
             // Only rows 3 and 4 can scroll
             3..=4 if (y & 2) == 0 => W,
-            2..=4 => W + scrollable,
-            _ => unimplemented!("1 and 5+ line displays are not covered by StandardMemoryMap"),
+            _ => W + scrollable,
         }
     }
 
@@ -70,10 +90,41 @@ impl<const W: u8, const H: u8, const L: u8> DisplayMemoryMap for StandardMemoryM
     fn has_two_memory_lines(&self) -> bool {
         H > 1
     }
+
+    fn xy_for_address(&self, address: u8) -> Option<(u8, u8)> {
+        let (line, offset) = match address {
+            0x00..0x28 => (0, address),
+            0x40..0x68 => (1, address - 0x40),
+            _ => return None,
+        };
+
+        // Rows 3 and 4 continue the memory lines of rows 1 and 2.
+        let (x, y) = if H > 2 && offset >= W {
+            (offset - W, line + 2)
+        } else {
+            (offset, line)
+        };
+        (y < H && x < self.line_width(y)).then_some((x, y))
+    }
 }
 
 const fn scrollable_margin(w: u8, h: u8, l: u8) -> u8 {
-    l - w * ((h + 1) / 2)
+    l - w * h.div_ceil(2)
+}
+
+/// The DDRAM address the controller moves to after writing to `address` in increment mode.
+///
+/// In two-line mode, the first line spans `0x00..0x28` and the second line `0x40..0x68`.
+/// Otherwise, the single line spans `0x00..0x50`. The upper bit, which selects the second
+/// controller of a [`DualControllerMemoryMap`], is kept.
+pub(crate) const fn next_ddram_address(address: u8, two_lines: bool) -> u8 {
+    let controller = address & 0x80;
+    let next = match (address & 0x7f, two_lines) {
+        (0x27, true) => 0x40,
+        (0x67.., true) | (0x4f.., false) => 0x00,
+        (address, _) => address + 1,
+    };
+    controller | next
 }
 
 /// Memory Map for single-row displays that are using one line / contiguous memory.
@@ -114,13 +165,76 @@ impl<const W: u8, const L: u8> DisplayMemoryMap for Contiguous1RMemoryMap<W, L>
     fn has_two_memory_lines(&self) -> bool {
         false
     }
+
+    fn xy_for_address(&self, address: u8) -> Option<(u8, u8)> {
+        (address < L).then_some((address, 0))
+    }
+}
+
+/// Memory Map for 4-row displays with two controllers of two rows each, such as the 4004.
+///
+/// The rows of the second controller have the upper bit of their address set, which
+/// [`LcdDualControllerBus`](crate::bus::LcdDualControllerBus) uses to select its enable line.
+pub struct DualControllerMemoryMap<const WIDTH: u8>;
+
+impl<const W: u8> DualControllerMemoryMap<W> {
+    /// The address bit of the second controller.
+    pub const SECOND_CONTROLLER: u8 = 0x80;
+
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<const W: u8> Default for DualControllerMemoryMap<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: u8> DisplayMemoryMap for DualControllerMemoryMap<W> {
+    /// Address of a character, with each controller using the mapping of a 2-row display.
+    fn address_for_xy(&self, x: u8, y: u8) -> Option<u8> {
+        let controller = StandardMemoryMap::<W, 2>::new();
+        match y {
+            0..2 => controller.address_for_xy(x, y),
+            2..4 => controller
+                .address_for_xy(x, y - 2)
+                .map(|address| address | Self::SECOND_CONTROLLER),
+            _ => None,
+        }
+    }
+
+    fn line_width(&self, _y: u8) -> u8 {
+        StandardMemoryMap::<W, 2>::new().line_width(0)
+    }
+
+    fn display_size(&self) -> (u8, u8) {
+        (W, 4)
+    }
+
+    fn has_two_memory_lines(&self) -> bool {
+        true
+    }
+
+    fn xy_for_address(&self, address: u8) -> Option<(u8, u8)> {
+        let (x, y) = StandardMemoryMap::<W, 2>::new().xy_for_address(address & 0x7f)?;
+        if address & Self::SECOND_CONTROLLER != 0 {
+            Some((x, y + 2))
+        } else {
+            Some((x, y))
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::memory_map::{DisplayMemoryMap, MemoryMap2004};
+    use crate::memory_map::{
+        DisplayMemoryMap, MemoryMap1601Contiguous, MemoryMap1602, MemoryMap1604, MemoryMap2004,
+        MemoryMap4004, StandardMemoryMap,
+    };
 
-    use super::scrollable_margin;
+    use super::{next_ddram_address, scrollable_margin};
 
     #[test]
     fn test_scrollable_margin_1602() {
@@ -140,6 +254,13 @@ mod tests {
         assert_eq!(res, 20);
     }
 
+    #[test]
+    fn test_line_width_single_line() {
+        let map = StandardMemoryMap::<16, 1, 80>::new();
+        assert_eq!(80, map.line_width(0));
+        assert_eq!(Some(0x30), map.address_for_xy(0x30, 0));
+    }
+
     #[test]
     fn test_scrollable_margin_2004() {
         let res = scrollable_margin(20, 4, 40);
@@ -178,4 +299,64 @@ mod tests {
         let map = MemoryMap2004::new();
         assert_eq!(None, map.address_for_xy(20, 4));
     }
+
+    #[test]
+    fn test_xy_for_address() {
+        let map = MemoryMap2004::new();
+        assert_eq!(Some((0, 0)), map.xy_for_address(0x00));
+        assert_eq!(Some((0, 2)), map.xy_for_address(0x14));
+        assert_eq!(Some((19, 3)), map.xy_for_address(0x67));
+        assert_eq!(None, map.xy_for_address(0x28));
+        assert_eq!(None, map.xy_for_address(0x68));
+    }
+
+    #[test]
+    fn test_xy_for_address_roundtrip() {
+        fn roundtrip(map: impl DisplayMemoryMap) {
+            for address in 0..0x80 {
+                if let Some((x, y)) = map.xy_for_address(address) {
+                    assert_eq!(Some(address), map.address_for_xy(x, y));
+                }
+            }
+            let (_, height) = map.display_size();
+            for y in 0..height {
+                for x in 0..map.line_width(y) {
+                    let address = map.address_for_xy(x, y).unwrap();
+                    assert_eq!(Some((x, y)), map.xy_for_address(address));
+                }
+            }
+        }
+
+        roundtrip(MemoryMap1601Contiguous::new());
+        roundtrip(MemoryMap1602::new());
+        roundtrip(MemoryMap1604::new());
+        roundtrip(MemoryMap2004::new());
+        roundtrip(MemoryMap4004::new());
+    }
+
+    #[test]
+    fn test_dual_controller_position() {
+        let map = MemoryMap4004::new();
+        assert_eq!(Some(0x67), map.address_for_xy(39, 1));
+        assert_eq!(Some(0x80), map.address_for_xy(0, 2));
+        assert_eq!(Some(0xc0), map.address_for_xy(0, 3));
+        assert_eq!(None, map.address_for_xy(40, 3));
+        assert_eq!(Some((39, 3)), map.xy_for_address(0xe7));
+    }
+
+    #[test]
+    fn test_next_ddram_address_two_lines() {
+        assert_eq!(1, next_ddram_address(0, true));
+        assert_eq!(0x40, next_ddram_address(0x27, true));
+        assert_eq!(0x41, next_ddram_address(0x40, true));
+        assert_eq!(0x00, next_ddram_address(0x67, true));
+        assert_eq!(0xc0, next_ddram_address(0xa7, true));
+        assert_eq!(0x80, next_ddram_address(0xe7, true));
+    }
+
+    #[test]
+    fn test_next_ddram_address_one_line() {
+        assert_eq!(0x28, next_ddram_address(0x27, false));
+        assert_eq!(0x00, next_ddram_address(0x4f, false));
+    }
 }
@@ -0,0 +1,341 @@
+//! Recording mocks of the embedded-hal traits used by the bus tests.
+
+#![allow(dead_code)]
+
+extern crate std;
+
+use core::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+use std::{collections::VecDeque, rc::Rc, vec::Vec};
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{self, ErrorType as PinErrorType, InputPin, OutputPin},
+    i2c::{ErrorKind, ErrorType as I2cErrorType, I2c, NoAcknowledgeSource, Operation},
+    spi::{self, ErrorType as SpiErrorType, SpiDevice},
+};
+
+#[cfg(feature = "blocking")]
+use crate::{
+    bus::{
+        blocking::{LcdInit, LcdWrite},
+        LcdRegisterSelect,
+    },
+    LcdDisplayMode, LcdEntryMode, LcdFunctionMode,
+};
+
+/// A delay that returns immediately.
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for NoDelay {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// A delay that returns immediately and adds up the time it was asked to wait.
+#[derive(Default)]
+pub struct CountingDelay {
+    pub ns: u64,
+}
+
+impl DelayNs for CountingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.ns += u64::from(ns);
+    }
+}
+
+/// A `uWrite` target that collects what is written to it.
+#[cfg(feature = "ufmt")]
+#[derive(Default)]
+pub struct UString(pub std::string::String);
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uWrite for UString {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+/// Polls a future that never has to wait on anything else to completion.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum I2cOp {
+    Write(u8, Vec<u8>),
+    Read(u8, usize),
+}
+
+/// An I2C bus that records every operation, answers reads from a queue, and NACKs the
+/// addresses in `nack`.
+#[derive(Default)]
+pub struct MockI2c {
+    pub ops: Vec<I2cOp>,
+    pub reads: VecDeque<u8>,
+    pub nack: Vec<u8>,
+}
+
+impl MockI2c {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All bytes written to `address`, in order.
+    pub fn written(&self, address: u8) -> Vec<u8> {
+        self.writes(address).concat()
+    }
+
+    /// The individual writes to `address`, in order.
+    pub fn writes(&self, address: u8) -> Vec<Vec<u8>> {
+        self.ops
+            .iter()
+            .filter_map(|op| match op {
+                I2cOp::Write(a, bytes) if *a == address => Some(bytes.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl I2cErrorType for MockI2c {
+    type Error = ErrorKind;
+}
+
+impl I2c for MockI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if self.nack.contains(&address) {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+        let start = self.ops.len();
+        for operation in operations {
+            match operation {
+                // Adjacent writes of a transaction go out as one write.
+                Operation::Write(bytes) => {
+                    let merge = self.ops.len() > start;
+                    match self.ops.last_mut() {
+                        Some(I2cOp::Write(_, written)) if merge => written.extend_from_slice(bytes),
+                        _ => self.ops.push(I2cOp::Write(address, bytes.to_vec())),
+                    }
+                }
+                Operation::Read(buffer) => {
+                    self.ops.push(I2cOp::Read(address, buffer.len()));
+                    for byte in buffer.iter_mut() {
+                        *byte = self.reads.pop_front().unwrap_or(0);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for MockI2c {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        I2c::transaction(self, address, operations)
+    }
+}
+
+/// An SPI device that records the bytes of every write.
+#[derive(Default)]
+pub struct MockSpi {
+    pub writes: Vec<Vec<u8>>,
+}
+
+impl SpiErrorType for MockSpi {
+    type Error = spi::ErrorKind;
+}
+
+impl SpiDevice for MockSpi {
+    fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        for operation in operations {
+            if let spi::Operation::Write(bytes) = operation {
+                self.writes.push(bytes.to_vec());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::spi::SpiDevice for MockSpi {
+    async fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        SpiDevice::transaction(self, operations)
+    }
+}
+
+/// The shared log of pin level changes, as `(name, high)`.
+pub type PinLog = Rc<RefCell<Vec<(&'static str, bool)>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockPinError;
+
+impl digital::Error for MockPinError {
+    fn kind(&self) -> digital::ErrorKind {
+        digital::ErrorKind::Other
+    }
+}
+
+/// A pin that records the levels it is driven to, reads back `input`, and fails every
+/// operation when `failing` is set.
+pub struct MockPin {
+    pub name: &'static str,
+    pub log: PinLog,
+    pub input: bool,
+    pub failing: bool,
+}
+
+impl MockPin {
+    pub fn new(name: &'static str, log: &PinLog) -> Self {
+        Self {
+            name,
+            log: log.clone(),
+            input: false,
+            failing: false,
+        }
+    }
+
+    pub fn failing(mut self) -> Self {
+        self.failing = true;
+        self
+    }
+
+    fn check(&self) -> Result<(), MockPinError> {
+        match self.failing {
+            true => Err(MockPinError),
+            false => Ok(()),
+        }
+    }
+}
+
+impl PinErrorType for MockPin {
+    type Error = MockPinError;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.check()?;
+        self.log.borrow_mut().push((self.name, false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.check()?;
+        self.log.borrow_mut().push((self.name, true));
+        Ok(())
+    }
+}
+
+impl InputPin for MockPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.check()?;
+        Ok(self.input)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
+
+/// A driver bus that counts address changes and records the bytes written to the display
+/// memory. Clones share their counts, so they can be checked after the bus moved into a driver.
+/// Memory writes fail once `fail_after` of them succeeded.
+#[derive(Clone, Default)]
+pub struct CountingBus {
+    addresses: Rc<Cell<usize>>,
+    memory: Rc<RefCell<Vec<u8>>>,
+    pub fail_after: Option<usize>,
+}
+
+impl CountingBus {
+    pub fn failing_after(writes: usize) -> Self {
+        Self {
+            fail_after: Some(writes),
+            ..Self::default()
+        }
+    }
+
+    /// The number of DDRAM address changes.
+    pub fn addresses(&self) -> usize {
+        self.addresses.get()
+    }
+
+    /// The number of successful memory writes.
+    pub fn writes(&self) -> usize {
+        self.memory.borrow().len()
+    }
+
+    /// The bytes written to the display memory, in order.
+    pub fn memory(&self) -> Vec<u8> {
+        self.memory.borrow().clone()
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<Delay: ?Sized> LcdWrite<Delay> for CountingBus {
+    type Error = MockPinError;
+
+    fn write(
+        &mut self,
+        rs: LcdRegisterSelect,
+        data: u8,
+        _delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        match rs {
+            LcdRegisterSelect::Control if data & crate::SET_DDRAM_ADDRESS != 0 => {
+                self.addresses.set(self.addresses.get() + 1)
+            }
+            LcdRegisterSelect::Control => {}
+            LcdRegisterSelect::Memory if Some(self.writes()) == self.fail_after => {
+                return Err(MockPinError)
+            }
+            LcdRegisterSelect::Memory => self.memory.borrow_mut().push(data),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<Delay: ?Sized> LcdInit<Delay> for CountingBus {
+    fn init(
+        &mut self,
+        _function: LcdFunctionMode,
+        _display_mode: LcdDisplayMode,
+        _entry: LcdEntryMode,
+        _delay: &mut Delay,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+#![cfg(all(feature = "derive", feature = "blocking"))]
+
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{ErrorType, OutputPin},
+};
+use embedded_lcd::bus::{
+    blocking::LcdWrite, LcdParallelBus, LcdParallelPins, LcdParallelWriteOnly,
+};
+
+type Log = Rc<RefCell<Vec<(&'static str, bool)>>>;
+
+/// An output pin that records its levels in a log shared by all pins.
+struct Pin {
+    name: &'static str,
+    log: Log,
+}
+
+impl Pin {
+    fn new(name: &'static str, log: &Log) -> Self {
+        Self {
+            name,
+            log: log.clone(),
+        }
+    }
+}
+
+impl ErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl OutputPin for Pin {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push((self.name, true));
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.log.borrow_mut().push((self.name, false));
+        Ok(())
+    }
+}
+
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[derive(LcdParallelPins)]
+struct Pins4 {
+    #[lcd(rs)]
+    register_select: Pin,
+    #[lcd(rw)]
+    read_write: LcdParallelWriteOnly,
+    #[lcd(en)]
+    enable: Pin,
+    #[lcd(d4)]
+    data4: Pin,
+    #[lcd(d5)]
+    data5: Pin,
+    #[lcd(d6)]
+    data6: Pin,
+    #[lcd(d7)]
+    data7: Pin,
+}
+
+#[derive(LcdParallelPins)]
+struct Pins8(
+    #[lcd(d0)] Pin,
+    #[lcd(d1)] Pin,
+    #[lcd(d2)] Pin,
+    #[lcd(d3)] Pin,
+    #[lcd(d4)] Pin,
+    #[lcd(d5)] Pin,
+    #[lcd(d6)] Pin,
+    #[lcd(d7)] Pin,
+    #[lcd(rs)] Pin,
+    #[lcd(rw)] LcdParallelWriteOnly,
+    #[lcd(en)] Pin,
+);
+
+/// The levels of the data pins at every rising edge of E, with D0 as bit 0.
+fn latched(log: &Log) -> Vec<u8> {
+    let mut data = 0u8;
+    let mut latched = Vec::new();
+    for &(name, high) in log.borrow().iter() {
+        match name {
+            "E" if high => latched.push(data),
+            "E" | "RS" => {}
+            _ => {
+                let bit = 1 << (name.as_bytes()[1] - b'0');
+                data = if high { data | bit } else { data & !bit };
+            }
+        }
+    }
+    latched
+}
+
+#[test]
+fn test_derive_4bit() {
+    let log = Log::default();
+    let pins = Pins4 {
+        register_select: Pin::new("RS", &log),
+        read_write: LcdParallelWriteOnly,
+        enable: Pin::new("E", &log),
+        data4: Pin::new("D4", &log),
+        data5: Pin::new("D5", &log),
+        data6: Pin::new("D6", &log),
+        data7: Pin::new("D7", &log),
+    };
+    let mut bus = LcdParallelBus::new_4bit(pins);
+    bus.write_memory(0xa5, &mut NoDelay).unwrap();
+
+    assert_eq!(vec![0xa0, 0x50], latched(&log));
+    assert_eq!(Some(&("RS", true)), log.borrow().first());
+}
+
+#[test]
+fn test_derive_8bit() {
+    let log = Log::default();
+    let pins = Pins8(
+        Pin::new("D0", &log),
+        Pin::new("D1", &log),
+        Pin::new("D2", &log),
+        Pin::new("D3", &log),
+        Pin::new("D4", &log),
+        Pin::new("D5", &log),
+        Pin::new("D6", &log),
+        Pin::new("D7", &log),
+        Pin::new("RS", &log),
+        LcdParallelWriteOnly,
+        Pin::new("E", &log),
+    );
+    let mut bus = LcdParallelBus::new_8bit(pins);
+    bus.write_command(0x3c, &mut NoDelay).unwrap();
+
+    assert_eq!(vec![0x3c], latched(&log));
+    assert_eq!(Some(&("RS", false)), log.borrow().first());
+}